@@ -0,0 +1,119 @@
+//! Command line options shared by the binaries.
+
+use std::error::Error;
+use std::io::{self, Read};
+use std::rc::Rc;
+use std::time::Duration;
+use sudoku_evaluator::{Budget, Profile, SolveObserver, SolverBuilder, strategy::StrategyKind};
+
+const USAGE: &str = "usage: [--profile FILE] [--only NAME,NAME...] [--time-limit SECONDS] \
+                     [--max-steps N] [--trace] [PUZZLE]";
+
+/// Options given on the command line.
+pub struct Options {
+    /// The puzzle, with whitespace removed.
+    pub puzzle: String,
+    profile: Option<String>,
+    only: Option<Vec<StrategyKind>>,
+    budget: Budget,
+    trace: bool,
+}
+
+impl Options {
+    /// Parse `[--profile FILE] [--only NAME,NAME...] [--time-limit SECONDS]
+    /// [--max-steps N] [--trace] [PUZZLE]`, reading the puzzle from stdin
+    /// when it is not given.
+    pub fn from_args() -> Result<Self, Box<dyn Error>> {
+        let mut args = std::env::args().skip(1);
+        let mut profile = None;
+        let mut only = None;
+        let mut budget = Budget::default();
+        let mut trace = false;
+        let mut puzzle_arg = None;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--profile" => {
+                    profile = Some(args.next().ok_or("--profile expects a file path")?);
+                }
+                "--only" => {
+                    let names = args.next().ok_or("--only expects strategy names")?;
+                    let kinds = names
+                        .split(',')
+                        .map(|name| name.trim().parse())
+                        .collect::<Result<Vec<StrategyKind>, _>>()?;
+                    only = Some(kinds);
+                }
+                "--time-limit" => {
+                    let seconds = args.next().ok_or("--time-limit expects seconds")?;
+                    budget = budget.with_time_limit(Duration::try_from_secs_f64(seconds.parse()?)?);
+                }
+                "--max-steps" => {
+                    let steps = args.next().ok_or("--max-steps expects a number")?;
+                    budget = budget.with_max_steps(steps.parse()?);
+                }
+                "--trace" => trace = true,
+                unknown if unknown.starts_with("--") => {
+                    eprintln!("{}", USAGE);
+                    return Err(format!("unknown option {}", unknown).into());
+                }
+                _ => puzzle_arg = Some(arg),
+            }
+        }
+        let input = match puzzle_arg {
+            Some(puzzle) => puzzle,
+            None => {
+                let mut buf = String::new();
+                io::stdin().read_to_string(&mut buf)?;
+                buf
+            }
+        };
+        Ok(Self {
+            puzzle: input.chars().filter(|c| !c.is_whitespace()).collect(),
+            profile,
+            only,
+            budget,
+            trace,
+        })
+    }
+
+    /// The strategies of the profile, or `fallback` without one, narrowed to
+    /// those given with `--only` and the `kept` ones, limited by the budget
+    /// options and traced with `--trace`.
+    pub fn builder(
+        &self,
+        fallback: impl FnOnce() -> SolverBuilder,
+        kept: &[StrategyKind],
+    ) -> Result<SolverBuilder, Box<dyn Error>> {
+        let builder = match &self.profile {
+            Some(path) => SolverBuilder::from_profile(&Profile::load(path)?)?,
+            None => fallback(),
+        };
+        let builder = match &self.only {
+            Some(kinds) => builder.only(&[kinds.as_slice(), kept].concat()),
+            None => builder,
+        };
+        let builder = builder.budget(self.budget);
+        Ok(if self.trace {
+            builder.observer(Rc::new(Trace))
+        } else {
+            builder
+        })
+    }
+}
+
+/// Prints every strategy application that changed the board to stderr.
+struct Trace;
+
+impl SolveObserver for Trace {
+    fn after_apply(&self, kind: StrategyKind, changed: bool, elapsed: Duration) {
+        if changed {
+            eprintln!("{} ({:.3} ms)", kind, elapsed.as_secs_f64() * 1000.0);
+        }
+    }
+}
+
+/// The strategies separated by commas.
+pub fn list(kinds: &[StrategyKind]) -> String {
+    let names: Vec<_> = kinds.iter().map(ToString::to_string).collect();
+    names.join(", ")
+}
@@ -1,22 +1,18 @@
-use std::env;
-use std::io::{self, Read};
-use sudoku_evaluator::{ProgressiveSolver, board::Board};
+mod cli;
+
+use sudoku_evaluator::{ProgressiveSolver, SolverBuilder, board::Board, strategy::StrategyKind};
+
+/// The ladder starts from the singles, so `--only` keeps them.
+const SINGLES: [StrategyKind; 2] = [StrategyKind::SingleCandidate, StrategyKind::HiddenSingle];
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args: Vec<String> = env::args().collect();
-    let input = if args.len() > 1 {
-        args[1].clone()
-    } else {
-        let mut buf = String::new();
-        io::stdin().read_to_string(&mut buf)?;
-        buf
-    };
-    let puzzle: String = input.chars().filter(|c| !c.is_whitespace()).collect();
-    let mut board = Board::parse(&puzzle)?;
-    let solver = ProgressiveSolver::default();
+    let options = cli::Options::from_args()?;
+    let mut board = Board::parse(&options.puzzle)?;
+    let ladder = options.builder(SolverBuilder::with_default_strategies, &SINGLES)?;
+    let solver = ProgressiveSolver::from(ladder.build());
     match solver.solve(&mut board) {
         Ok(kinds) => {
-            println!("Solved with strategies: {:?}", kinds);
+            println!("Solved with strategies: {}", cli::list(&kinds));
             println!("{}", board);
         }
         Err(e) => {
@@ -1,21 +1,22 @@
-use std::io::{self, Read};
-use sudoku_evaluator::{Solver, board::Board};
+mod cli;
+
+use sudoku_evaluator::{SolverBuilder, board::Board};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args: Vec<String> = std::env::args().collect();
-    let input = if args.len() > 1 {
-        args[1].clone()
-    } else {
-        let mut buf = String::new();
-        io::stdin().read_to_string(&mut buf)?;
-        buf
-    };
-    let puzzle: String = input.chars().filter(|c| !c.is_whitespace()).collect();
-    let mut board = Board::parse(&puzzle)?;
-    let solver = Solver::default();
-    match solver.solve(&mut board) {
-        Ok(strategies) => {
-            println!("Solved with strategies: {:?}", strategies);
+    let options = cli::Options::from_args()?;
+    let mut board = Board::parse(&options.puzzle)?;
+    let solver = options
+        .builder(SolverBuilder::with_default_strategies, &[])?
+        .build();
+    match solver.solve_with_report(&mut board) {
+        Ok(report) => {
+            println!("Solved with strategies: {}", cli::list(&report.kinds()));
+            for usage in &report.usages {
+                println!(
+                    "  {}: {} steps, {} placements, {} eliminations",
+                    usage.kind, usage.applications, usage.placements, usage.eliminations
+                );
+            }
             println!("{}", board);
         }
         Err(e) => {
@@ -24,6 +24,12 @@ impl CandidateSet {
     pub fn union(self, other: CandidateSet) -> CandidateSet {
         CandidateSet(self.0 | other.0)
     }
+    pub fn intersection(self, other: CandidateSet) -> CandidateSet {
+        CandidateSet(self.0 & other.0)
+    }
+    pub fn difference(self, other: CandidateSet) -> CandidateSet {
+        CandidateSet(self.0 & !other.0)
+    }
     pub fn is_empty(self) -> bool {
         self.0 == 0
     }
@@ -8,11 +8,16 @@ use super::{Digit, candidate::*, unit::*};
 pub struct Cell {
     pub value: Option<Digit>,
     removed: u16,
+    given: bool,
 }
 
 impl Cell {
     pub(crate) fn new(value: Option<Digit>) -> Self {
-        Cell { value, removed: 0 }
+        Cell {
+            value,
+            removed: 0,
+            given: value.is_some(),
+        }
     }
 }
 
@@ -30,6 +35,12 @@ impl Board {
         self.cells[r][c].value
     }
 
+    /// Check whether the cell was filled in the original puzzle rather
+    /// than placed while solving.
+    pub fn is_given(&self, r: usize, c: usize) -> bool {
+        self.cells[r][c].given
+    }
+
     pub fn set(&mut self, r: usize, c: usize, val: Digit) {
         self.cells[r][c].value = Some(val);
         self.cells[r][c].removed = 0;
@@ -58,6 +69,11 @@ impl Board {
         set
     }
 
+    /// Check whether the digit was marked as removed from the cell.
+    pub(crate) fn is_removed(&self, r: usize, c: usize, d: Digit) -> bool {
+        self.cells[r][c].removed & (1 << (d - 1)) != 0
+    }
+
     pub fn eliminate_candidate(&mut self, r: usize, c: usize, d: Digit) -> Option<bool> {
         if self.get(r, c).is_some() {
             return Some(false);
@@ -269,6 +285,25 @@ impl Board {
             .collect()
     }
 
+    /// Check whether two different cells share a row, column or box.
+    pub fn sees(&self, (r1, c1): (usize, usize), (r2, c2): (usize, usize)) -> bool {
+        (r1, c1) != (r2, c2) && (r1 == r2 || c1 == c2 || (r1 / 3 == r2 / 3 && c1 / 3 == c2 / 3))
+    }
+
+    /// Cells seeing every one of the given cells, excluding the cells themselves.
+    pub fn common_peers(&self, cells: &[(usize, usize)]) -> Vec<(usize, usize)> {
+        let mut common = match cells.first() {
+            Some(&(r, c)) => self.peer_coords(r, c),
+            None => return Vec::new(),
+        };
+        for &(r, c) in &cells[1..] {
+            let peers = self.peer_coords(r, c);
+            common.retain(|p| peers.contains(p));
+        }
+        common.retain(|p| !cells.contains(p));
+        common
+    }
+
     pub(crate) fn row_values(&self, r: usize) -> impl Iterator<Item = Digit> + '_ {
         self.cells[r].iter().filter_map(|c| c.value)
     }
@@ -293,6 +328,15 @@ impl Board {
     }
 }
 
+/// Shows the values of the board, as [`Display`](fmt::Display) does.
+impl fmt::Debug for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Board")
+            .field(&format_args!("{}", self))
+            .finish()
+    }
+}
+
 impl fmt::Display for Board {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for r in 0..9 {
@@ -306,3 +350,25 @@ impl fmt::Display for Board {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_lists_placed_digits_marked_removed() {
+        let mut board = Board::parse(&".".repeat(81)).unwrap();
+        board.set(0, 0, 1);
+        assert!(board.validate().removed_placed.is_empty());
+        // a cell built holding 1 with 1 marked as removed
+        board.cells[0][0].removed = 1;
+        let report = board.validate();
+        assert_eq!(report.removed_placed, vec![(0, 0, 1)]);
+        assert!(!report.is_ok());
+        assert!(
+            report
+                .to_string()
+                .contains("(0, 0) holds 1 after it was removed\n")
+        );
+    }
+}
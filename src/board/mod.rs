@@ -47,11 +47,13 @@ mod candidate;
 mod grid;
 mod parser;
 mod unit;
+mod validation;
 
 pub use candidate::*;
 pub use grid::Board;
 pub use parser::BoardError;
 pub use unit::{Unit, UnitIter};
+pub use validation::{Duplicate, ValidationReport};
 
 #[cfg(test)]
 mod tests {
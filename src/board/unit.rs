@@ -1,6 +1,8 @@
 //! Abstractions for iterating over rows, columns and boxes.
 
-#[derive(Clone, Copy)]
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Unit {
     Row(usize),
     Col(usize),
@@ -32,6 +34,16 @@ impl Unit {
     }
 }
 
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Unit::Row(r) => write!(f, "row {}", r),
+            Unit::Col(c) => write!(f, "column {}", c),
+            Unit::Box(r, c) => write!(f, "box at ({}, {})", r, c),
+        }
+    }
+}
+
 pub struct UnitIter {
     coords: [(usize, usize); 9],
     idx: usize,
@@ -0,0 +1,153 @@
+//! Detailed checks of a board, listing everything that is wrong with it.
+
+use super::{Digit, Unit, digits, grid::Board};
+use std::fmt;
+
+/// A digit placed more than once in a unit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Duplicate {
+    pub unit: Unit,
+    pub digit: Digit,
+    pub cells: Vec<(usize, usize)>,
+}
+
+impl fmt::Display for Duplicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cells: Vec<_> = self
+            .cells
+            .iter()
+            .map(|(r, c)| format!("({}, {})", r, c))
+            .collect();
+        write!(
+            f,
+            "{} holds {} more than once, at {}",
+            self.unit,
+            self.digit,
+            cells.join(", ")
+        )
+    }
+}
+
+/// Everything [`Board::validate`] found wrong with a board.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    pub duplicates: Vec<Duplicate>,
+    /// Unsolved cells left without any candidate.
+    pub empty_cells: Vec<(usize, usize)>,
+    /// Digits neither placed in a unit nor a candidate of any of its cells.
+    pub missing_digits: Vec<(Unit, Digit)>,
+    /// Unsolved cells of a unit whose removed candidates leave each of them
+    /// with the same single digit.
+    pub forced_twice: Vec<Duplicate>,
+    /// Placed digits still marked as removed from their cell. Placing a digit
+    /// with [`Board::set`] clears the marks, so this only catches cells built
+    /// with their marks directly.
+    pub removed_placed: Vec<(usize, usize, Digit)>,
+}
+
+impl ValidationReport {
+    /// Whether nothing is wrong with the board.
+    pub fn is_ok(&self) -> bool {
+        self.duplicates.is_empty()
+            && self.empty_cells.is_empty()
+            && self.missing_digits.is_empty()
+            && self.forced_twice.is_empty()
+            && self.removed_placed.is_empty()
+    }
+}
+
+/// One problem per line.
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for duplicate in &self.duplicates {
+            writeln!(f, "{}", duplicate)?;
+        }
+        for (r, c) in &self.empty_cells {
+            writeln!(f, "({}, {}) has no candidates left", r, c)?;
+        }
+        for (unit, digit) in &self.missing_digits {
+            writeln!(f, "{} has no place left for {}", unit, digit)?;
+        }
+        for forced in &self.forced_twice {
+            let cells: Vec<_> = forced
+                .cells
+                .iter()
+                .map(|(r, c)| format!("({}, {})", r, c))
+                .collect();
+            writeln!(
+                f,
+                "{} leaves {} as the only candidate of {}",
+                forced.unit,
+                forced.digit,
+                cells.join(", ")
+            )?;
+        }
+        for (r, c, digit) in &self.removed_placed {
+            writeln!(f, "({}, {}) holds {} after it was removed", r, c, digit)?;
+        }
+        Ok(())
+    }
+}
+
+impl Board {
+    /// Every digit placed more than once in a unit, with the cells holding
+    /// it.
+    pub fn duplicates(&self) -> Vec<Duplicate> {
+        let mut found = Vec::new();
+        for unit in Unit::all() {
+            for digit in digits() {
+                let cells: Vec<_> = self
+                    .unit_iter(unit)
+                    .filter(|&(r, c)| self.get(r, c) == Some(digit))
+                    .collect();
+                if cells.len() > 1 {
+                    found.push(Duplicate { unit, digit, cells });
+                }
+            }
+        }
+        found
+    }
+
+    /// List every problem with the board, unlike [`Board::is_valid`] which
+    /// only checks for duplicates.
+    pub fn validate(&self) -> ValidationReport {
+        let empty_cells = self
+            .unsolved_cells()
+            .filter(|&(r, c)| self.candidates(r, c).is_empty())
+            .collect();
+        let missing_digits = Unit::all()
+            .flat_map(|unit| digits().map(move |digit| (unit, digit)))
+            .filter(|&(unit, digit)| {
+                self.unit_iter(unit).all(|(r, c)| match self.get(r, c) {
+                    Some(value) => value != digit,
+                    None => !self.candidates(r, c).contains(digit),
+                })
+            })
+            .collect();
+        let mut forced_twice = Vec::new();
+        for unit in Unit::all() {
+            for digit in digits() {
+                let cells: Vec<_> = self
+                    .unsolved_in_unit(unit)
+                    .filter(|(_, cands)| cands.len() == 1 && cands.contains(digit))
+                    .map(|(cell, _)| cell)
+                    .collect();
+                if cells.len() > 1 {
+                    forced_twice.push(Duplicate { unit, digit, cells });
+                }
+            }
+        }
+        let removed_placed = self
+            .cells()
+            .filter_map(|(r, c)| self.get(r, c).map(|d| (r, c, d)))
+            .filter(|&(r, c, d)| self.is_removed(r, c, d))
+            .collect();
+        ValidationReport {
+            duplicates: self.duplicates(),
+            empty_cells,
+            missing_digits,
+            forced_twice,
+            removed_placed,
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! Bounds on how long a solve may run, and stopping it from outside.
+
+use crate::SolverError;
+use crate::report::SolveReport;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Limits on a single solve. A solver stops with
+/// [`SolverError::BudgetExceeded`] once either is reached, leaving the board
+/// as the steps taken so far left it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Budget {
+    /// Wall-clock time allowed from the start of the solve.
+    pub time_limit: Option<Duration>,
+    /// Largest number of steps taken.
+    pub max_steps: Option<usize>,
+}
+
+impl Budget {
+    /// No limit at all.
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    pub fn with_time_limit(mut self, limit: Duration) -> Self {
+        self.time_limit = Some(limit);
+        self
+    }
+
+    pub fn with_max_steps(mut self, steps: usize) -> Self {
+        self.max_steps = Some(steps);
+        self
+    }
+
+    /// Fail when the steps in the report already use up the step limit, so
+    /// another step changing the board is one too many.
+    pub(crate) fn check_steps(&self, report: &SolveReport) -> Result<(), SolverError> {
+        if self.max_steps.is_some_and(|max| report.steps >= max) {
+            Err(exceeded(report))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Start the clock of a solve and watch its token, within the context
+    /// of the solve running it, if any. The context stops the solve once
+    /// its own time limit or that of an enclosing solve has passed, or once
+    /// any of their tokens is cancelled.
+    pub(crate) fn start(&self, cancel: Option<&CancelToken>, outer: &SolveContext) -> SolveContext {
+        let mut context = outer.clone();
+        if let Some(limit) = self.time_limit {
+            let deadline = Instant::now() + limit;
+            context.deadline = Some(outer.deadline.map_or(deadline, |d| d.min(deadline)));
+        }
+        context.tokens.extend(cancel.cloned());
+        context
+    }
+}
+
+/// Handle for cancelling a solve from another thread. Clones share the same
+/// flag.
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken {
+    flag: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask the solves using the token to stop. They return
+    /// [`SolverError::Cancelled`] before their next step.
+    pub fn cancel(&self) {
+        self.flag.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.flag.load(Ordering::Relaxed)
+    }
+}
+
+/// Deadline and cancel tokens of a running solve, handed to the strategies
+/// it applies so the inner solvers they follow assumptions with stop along
+/// with it. The default context never stops.
+#[derive(Clone, Debug, Default)]
+pub struct SolveContext {
+    deadline: Option<Instant>,
+    tokens: Vec<CancelToken>,
+}
+
+impl SolveContext {
+    /// Fail when the solve was cancelled or its deadline has passed.
+    pub(crate) fn check(&self, report: &SolveReport) -> Result<(), SolverError> {
+        if self.tokens.iter().any(CancelToken::is_cancelled) {
+            Err(cancelled(report))
+        } else if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            Err(exceeded(report))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// [`SolveContext::check`] for strategies that search for a long time
+    /// between steps. The solver taking the step reports its progress in
+    /// place of the empty one.
+    pub fn check_interrupted(&self) -> Result<(), SolverError> {
+        self.check(&SolveReport::default())
+    }
+}
+
+/// Report an interrupted inner search as the progress of the solve that
+/// got as far as the report.
+pub(crate) fn rebase(error: SolverError, report: &SolveReport) -> SolverError {
+    match error {
+        SolverError::BudgetExceeded { .. } => exceeded(report),
+        SolverError::Cancelled { .. } => cancelled(report),
+        e => e,
+    }
+}
+
+/// The budget error for a solve that got as far as the report.
+pub(crate) fn exceeded(report: &SolveReport) -> SolverError {
+    SolverError::BudgetExceeded {
+        steps: report.steps,
+        strategies: report.kinds(),
+    }
+}
+
+/// The cancellation error for a solve that got as far as the report.
+pub(crate) fn cancelled(report: &SolveReport) -> SolverError {
+    SolverError::Cancelled {
+        steps: report.steps,
+        strategies: report.kinds(),
+    }
+}
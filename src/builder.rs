@@ -0,0 +1,110 @@
+//! Assembling a [`Solver`] from strategy kinds, configured strategies and
+//! profiles.
+
+use crate::profile::{Profile, ProfileError};
+use crate::strategy::{self, Strategy, StrategyKind};
+use crate::{Budget, CancelToken, SolveObserver, SolvePolicy, Solver};
+use std::rc::Rc;
+
+/// Builder collecting the strategies of a [`Solver`] in the order they are
+/// tried.
+#[derive(Default)]
+pub struct SolverBuilder {
+    strategies: Vec<Box<dyn Strategy>>,
+    policy: SolvePolicy,
+    budget: Budget,
+    cancel: Option<CancelToken>,
+    observers: Vec<Rc<dyn SolveObserver>>,
+}
+
+impl SolverBuilder {
+    /// A builder without any strategy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A builder with every strategy in the canonical difficulty order, as
+    /// [`Solver::default`].
+    pub fn with_default_strategies() -> Self {
+        Self::new().kinds(strategy::ALL_KINDS)
+    }
+
+    /// A builder with the strategies and policy of the profile.
+    pub fn from_profile(profile: &Profile) -> Result<Self, ProfileError> {
+        Ok(Self {
+            strategies: profile.strategies()?,
+            policy: profile.policy()?,
+            budget: Budget::default(),
+            cancel: None,
+            observers: Vec::new(),
+        })
+    }
+
+    /// Append the strategy of the given kind with its default parameters.
+    pub fn kind(self, kind: StrategyKind) -> Self {
+        self.strategy(strategy::kind_to_strategy(kind))
+    }
+
+    /// Append the strategies of the given kinds with their default parameters.
+    pub fn kinds<I>(self, kinds: I) -> Self
+    where
+        I: IntoIterator<Item = StrategyKind>,
+    {
+        kinds.into_iter().fold(self, Self::kind)
+    }
+
+    /// Append a configured strategy.
+    pub fn strategy(mut self, strategy: Box<dyn Strategy>) -> Self {
+        self.strategies.push(strategy);
+        self
+    }
+
+    /// Drop every strategy of the given kind added so far.
+    pub fn without(mut self, kind: StrategyKind) -> Self {
+        self.strategies.retain(|s| s.kind() != kind);
+        self
+    }
+
+    /// Keep only the strategies of the given kinds, in their current order.
+    pub fn only(mut self, kinds: &[StrategyKind]) -> Self {
+        self.strategies.retain(|s| kinds.contains(&s.kind()));
+        self
+    }
+
+    /// Move through the strategies according to the policy.
+    pub fn policy(mut self, policy: SolvePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Stop solving once the budget runs out.
+    pub fn budget(mut self, budget: Budget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Stop solving once the token is cancelled.
+    pub fn cancel_token(mut self, token: CancelToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Notify the observer around every strategy application.
+    pub fn observer(mut self, observer: Rc<dyn SolveObserver>) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    pub fn build(self) -> Solver {
+        let solver = Solver::new(self.strategies)
+            .with_policy(self.policy)
+            .with_budget(self.budget);
+        let solver = match self.cancel {
+            Some(token) => solver.with_cancel_token(token),
+            None => solver,
+        };
+        self.observers
+            .into_iter()
+            .fold(solver, Solver::with_observer)
+    }
+}
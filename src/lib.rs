@@ -6,133 +6,369 @@
 
 pub mod board;
 pub use board::BoardError;
+pub mod budget;
+pub mod builder;
+pub mod observer;
+pub mod policy;
+pub mod profile;
 pub mod progressive;
+pub mod report;
 pub mod strategy;
-pub use progressive::ProgressiveSolver;
+pub use budget::{Budget, CancelToken, SolveContext};
+pub use builder::SolverBuilder;
+pub use observer::SolveObserver;
+pub use policy::SolvePolicy;
+pub use profile::{Profile, ProfileError};
+pub use progressive::{Bottleneck, ProgressiveSolver};
+pub use report::{SolveReport, SolveStep, StrategyUsage};
 
-use board::Board;
+use board::{Board, Digit, Duplicate, Unit};
 use std::error::Error;
+use std::rc::Rc;
+use std::time::Instant;
 use strategy::{Strategy, StrategyKind};
 
 /// Errors that can occur while solving a puzzle.
 #[derive(Debug)]
 pub enum SolverError {
     /// The board was found to contain a contradiction at the given cell.
-    Contradiction { row: usize, col: usize },
-    /// The initial board was invalid.
-    InvalidBoard,
+    Contradiction {
+        row: usize,
+        col: usize,
+        /// Digit being placed or removed when the contradiction showed, when
+        /// the strategy knows it.
+        digit: Option<Digit>,
+        /// Unit the strategy was working in, when it knows it.
+        unit: Option<Unit>,
+        /// Cells whose candidates rule out the one at the contradiction,
+        /// when the strategy knows them.
+        cells: Vec<(usize, usize)>,
+        /// Strategy that ran into it, filled in by the solver applying it.
+        strategy: Option<StrategyKind>,
+        /// The board before the failing step, filled in by the solver.
+        board: Option<Box<Board>>,
+    },
+    /// The initial board places a digit more than once in a unit.
+    InvalidBoard {
+        /// Every digit placed more than once, as [`Board::duplicates`] lists
+        /// them.
+        duplicates: Vec<Duplicate>,
+    },
     /// The puzzle could not be solved with the available strategies.
     Unsolvable,
+    /// The solve ran out of its [`Budget`]. The board keeps the progress
+    /// made by the steps taken.
+    BudgetExceeded {
+        steps: usize,
+        /// Strategies used so far, in the order they were first needed.
+        strategies: Vec<StrategyKind>,
+    },
+    /// The solve was stopped through its [`CancelToken`]. The board keeps
+    /// the progress made by the steps taken.
+    Cancelled {
+        steps: usize,
+        /// Strategies used so far, in the order they were first needed.
+        strategies: Vec<StrategyKind>,
+    },
 }
 
 impl std::fmt::Display for SolverError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            SolverError::Contradiction { row, col } => {
-                write!(f, "contradiction at ({}, {})", row, col)
+            SolverError::Contradiction {
+                row,
+                col,
+                digit,
+                unit,
+                strategy,
+                ..
+            } => {
+                write!(f, "contradiction at ({}, {})", row, col)?;
+                if let Some(digit) = digit {
+                    write!(f, " on digit {}", digit)?;
+                }
+                if let Some(unit) = unit {
+                    write!(f, " in {}", unit)?;
+                }
+                if let Some(strategy) = strategy {
+                    write!(f, " found by {}", strategy)?;
+                }
+                Ok(())
+            }
+            SolverError::InvalidBoard { duplicates } => {
+                let duplicates: Vec<_> = duplicates.iter().map(|d| d.to_string()).collect();
+                write!(f, "board is invalid: {}", duplicates.join("; "))
             }
-            SolverError::InvalidBoard => write!(f, "board is invalid"),
             SolverError::Unsolvable => {
                 write!(f, "puzzle cannot be solved with available strategies")
             }
+            SolverError::BudgetExceeded { steps, .. } => {
+                write!(f, "solve budget exceeded after {} steps", steps)
+            }
+            SolverError::Cancelled { steps, .. } => {
+                write!(f, "solve cancelled after {} steps", steps)
+            }
         }
     }
 }
 
 impl Error for SolverError {}
 
+impl SolverError {
+    /// Fail with the duplicates of a board that is not valid.
+    pub(crate) fn check_valid(board: &Board) -> Result<(), Self> {
+        if board.is_valid() {
+            Ok(())
+        } else {
+            Err(SolverError::InvalidBoard {
+                duplicates: board.duplicates(),
+            })
+        }
+    }
+
+    pub(crate) fn contradiction(row: usize, col: usize, digit: Option<Digit>) -> Self {
+        SolverError::Contradiction {
+            row,
+            col,
+            digit,
+            unit: None,
+            cells: Vec::new(),
+            strategy: None,
+            board: None,
+        }
+    }
+
+    /// Fill in the unit a contradiction was found in and the cells ruling
+    /// out the candidate.
+    pub(crate) fn in_unit(self, unit: Option<Unit>, cells: Vec<(usize, usize)>) -> Self {
+        match self {
+            SolverError::Contradiction {
+                row,
+                col,
+                digit,
+                strategy,
+                board,
+                ..
+            } => SolverError::Contradiction {
+                row,
+                col,
+                digit,
+                unit,
+                cells,
+                strategy,
+                board,
+            },
+            e => e,
+        }
+    }
+
+    /// Fill in the strategy and board of a contradiction found applying the
+    /// strategy of the given kind to `before`.
+    pub(crate) fn in_step(self, kind: StrategyKind, before: &Board) -> Self {
+        match self {
+            SolverError::Contradiction {
+                row,
+                col,
+                digit,
+                unit,
+                cells,
+                strategy,
+                board,
+            } => SolverError::Contradiction {
+                row,
+                col,
+                digit,
+                unit,
+                cells,
+                strategy: strategy.or(Some(kind)),
+                board: board.or_else(|| Some(Box::new(before.clone()))),
+            },
+            e => e,
+        }
+    }
+}
+
 pub struct Solver {
-    strategies: Vec<Box<dyn Strategy>>,
+    strategies: Vec<Rc<dyn Strategy>>,
+    policy: SolvePolicy,
+    budget: Budget,
+    cancel: Option<CancelToken>,
+    observers: Vec<Rc<dyn SolveObserver>>,
 }
 
+/// Strategies that follow assumptions with an inner solver.
+const ASSUMING_KINDS: [StrategyKind; 5] = [
+    StrategyKind::Nishio,
+    StrategyKind::KrakenFish,
+    StrategyKind::CellForcingChain,
+    StrategyKind::UnitForcingChain,
+    StrategyKind::DynamicForcingChain,
+];
+
 impl Solver {
     pub fn new(strategies: Vec<Box<dyn Strategy>>) -> Self {
-        Self { strategies }
+        Self {
+            strategies: strategies.into_iter().map(Rc::from).collect(),
+            policy: SolvePolicy::default(),
+            budget: Budget::default(),
+            cancel: None,
+            observers: Vec::new(),
+        }
+    }
+
+    /// Move through the strategies according to the policy.
+    pub fn with_policy(mut self, policy: SolvePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn policy(&self) -> SolvePolicy {
+        self.policy
+    }
+
+    /// Stop solving once the budget runs out.
+    pub fn with_budget(mut self, budget: Budget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    pub fn budget(&self) -> Budget {
+        self.budget
+    }
+
+    /// Stop solving once the token is cancelled.
+    pub fn with_cancel_token(mut self, token: CancelToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Notify the observer around every strategy application.
+    pub fn with_observer(mut self, observer: Rc<dyn SolveObserver>) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    /// Start assembling a solver strategy by strategy.
+    pub fn builder() -> SolverBuilder {
+        SolverBuilder::new()
     }
 
     pub fn with_default_strategies() -> Self {
         Self::default()
     }
 
+    /// Same as [`Solver::without_nishio_and_forcing_chain`].
     pub fn without_nishio() -> Self {
-        Self::new(vec![
-            Box::new(strategy::single_candidate::SingleCandidate),
-            Box::new(strategy::hidden_single::HiddenSingle),
-            Box::new(strategy::naked_pair::NakedPair),
-            Box::new(strategy::naked_triple::NakedTriple),
-            Box::new(strategy::naked_quad::NakedQuad),
-            Box::new(strategy::hidden_pair::HiddenPair),
-            Box::new(strategy::hidden_triple::HiddenTriple),
-            Box::new(strategy::hidden_quad::HiddenQuad),
-            Box::new(strategy::pointing_pair::PointingPair),
-            Box::new(strategy::box_line_reduction::BoxLineReduction),
-            Box::new(strategy::x_wing::XWing),
-            Box::new(strategy::y_wing::YWing),
-            Box::new(strategy::xyz_wing::XYZWing),
-            Box::new(strategy::xy_wing::XYWing),
-            Box::new(strategy::xy_chain::XYChain),
-            Box::new(strategy::simple_coloring::SimpleColoring),
-            Box::new(strategy::jellyfish::Jellyfish),
-            Box::new(strategy::unique_rectangle::UniqueRectangle),
-            Box::new(strategy::swordfish::Swordfish),
-            Box::new(strategy::bug::Bug),
-        ])
+        Self::without_nishio_and_forcing_chain()
     }
 
+    /// Every strategy except those making assumptions: Nishio, Kraken fish
+    /// and the forcing chains.
     pub fn without_nishio_and_forcing_chain() -> Self {
-        Self::new(vec![
-            Box::new(strategy::single_candidate::SingleCandidate),
-            Box::new(strategy::hidden_single::HiddenSingle),
-            Box::new(strategy::naked_pair::NakedPair),
-            Box::new(strategy::naked_triple::NakedTriple),
-            Box::new(strategy::naked_quad::NakedQuad),
-            Box::new(strategy::hidden_pair::HiddenPair),
-            Box::new(strategy::hidden_triple::HiddenTriple),
-            Box::new(strategy::hidden_quad::HiddenQuad),
-            Box::new(strategy::pointing_pair::PointingPair),
-            Box::new(strategy::box_line_reduction::BoxLineReduction),
-            Box::new(strategy::x_wing::XWing),
-            Box::new(strategy::y_wing::YWing),
-            Box::new(strategy::xyz_wing::XYZWing),
-            Box::new(strategy::xy_wing::XYWing),
-            Box::new(strategy::xy_chain::XYChain),
-            Box::new(strategy::simple_coloring::SimpleColoring),
-            Box::new(strategy::jellyfish::Jellyfish),
-            Box::new(strategy::unique_rectangle::UniqueRectangle),
-            Box::new(strategy::swordfish::Swordfish),
-            Box::new(strategy::bug::Bug),
-        ])
-    }
-
-    fn apply_strategies(&self, board: &mut Board) -> Result<Vec<StrategyKind>, SolverError> {
-        if !board.is_valid() {
-            return Err(SolverError::InvalidBoard);
+        ASSUMING_KINDS
+            .into_iter()
+            .fold(SolverBuilder::with_default_strategies(), |builder, kind| {
+                builder.without(kind)
+            })
+            .build()
+    }
+
+    /// Kinds of the strategies tried, in order.
+    pub fn kinds(&self) -> Vec<StrategyKind> {
+        self.strategies.iter().map(|s| s.kind()).collect()
+    }
+
+    fn apply_strategies(
+        &self,
+        board: &mut Board,
+        outer: &SolveContext,
+    ) -> Result<SolveReport, SolverError> {
+        SolverError::check_valid(board)?;
+        let context = self.budget.start(self.cancel.as_ref(), outer);
+        let mut report = SolveReport::default();
+        match self.policy {
+            SolvePolicy::SimplestFirst => self.simplest_first(board, &context, &mut report)?,
+            SolvePolicy::RoundRobin => self.round_robin(board, &context, &mut report)?,
+            SolvePolicy::AllPerPass => self.all_per_pass(board, &context, &mut report)?,
+            SolvePolicy::MinimalLadder => {
+                progressive::minimal_ladder(self, board, &context, &mut report, None)?;
+            }
         }
-        let mut used = Vec::new();
+        Ok(report)
+    }
+
+    fn simplest_first(
+        &self,
+        board: &mut Board,
+        context: &SolveContext,
+        report: &mut SolveReport,
+    ) -> Result<(), SolverError> {
+        let mut before = board.clone();
         loop {
             let mut progress = false;
             for strat in &self.strategies {
-                let changed = strat.apply(board)?;
-                if changed {
-                    if !used.contains(&strat.kind()) {
-                        used.push(strat.kind());
-                    }
+                if self.step(strat.as_ref(), board, &mut before, context, report)? {
                     progress = true;
                     break;
                 }
             }
             if !progress {
+                return Ok(());
+            }
+        }
+    }
+
+    fn round_robin(
+        &self,
+        board: &mut Board,
+        context: &SolveContext,
+        report: &mut SolveReport,
+    ) -> Result<(), SolverError> {
+        let mut before = board.clone();
+        let mut idle = 0;
+        for strat in self.strategies.iter().cycle() {
+            if idle == self.strategies.len() {
                 break;
             }
+            if self.step(strat.as_ref(), board, &mut before, context, report)? {
+                idle = 0;
+            } else {
+                idle += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn all_per_pass(
+        &self,
+        board: &mut Board,
+        context: &SolveContext,
+        report: &mut SolveReport,
+    ) -> Result<(), SolverError> {
+        let mut before = board.clone();
+        loop {
+            let mut progress = false;
+            for strat in &self.strategies {
+                while self.step(strat.as_ref(), board, &mut before, context, report)? {
+                    progress = true;
+                }
+            }
+            if !progress {
+                return Ok(());
+            }
         }
-        Ok(used)
     }
 
     /// Attempt to fully solve the board.
     pub fn solve(&self, board: &mut Board) -> Result<Vec<StrategyKind>, SolverError> {
-        let used = self.apply_strategies(board)?;
+        self.solve_with_report(board).map(|report| report.kinds())
+    }
+
+    /// Attempt to fully solve the board, recording every step taken.
+    pub fn solve_with_report(&self, board: &mut Board) -> Result<SolveReport, SolverError> {
+        let report = self.apply_strategies(board, &SolveContext::default())?;
         if board.is_solved() {
-            Ok(used)
+            Ok(report)
         } else {
             Err(SolverError::Unsolvable)
         }
@@ -140,35 +376,98 @@ impl Solver {
 
     /// Apply strategies until no further progress can be made.
     pub fn reduce(&self, board: &mut Board) -> Result<Vec<StrategyKind>, SolverError> {
-        self.apply_strategies(board)
+        self.reduce_with_report(board).map(|report| report.kinds())
+    }
+
+    /// Apply strategies until no further progress can be made, recording
+    /// every step taken.
+    pub fn reduce_with_report(&self, board: &mut Board) -> Result<SolveReport, SolverError> {
+        self.apply_strategies(board, &SolveContext::default())
+    }
+
+    /// [`Solver::reduce_with_report`] as part of the solve running in the
+    /// context, stopping along with it.
+    pub fn reduce_in(
+        &self,
+        board: &mut Board,
+        context: &SolveContext,
+    ) -> Result<SolveReport, SolverError> {
+        self.apply_strategies(board, context)
+    }
+
+    /// Apply the strategy unless cancelled or out of budget, recording the
+    /// step when it changes the board. `before` holds the board as it was
+    /// before the step, since strategies leave the board alone unless they
+    /// report a change. Under [`SolvePolicy::AllPerPass`] a strategy listing
+    /// its deductions applies all of them in one step.
+    fn step(
+        &self,
+        strat: &dyn Strategy,
+        board: &mut Board,
+        before: &mut Board,
+        context: &SolveContext,
+        report: &mut SolveReport,
+    ) -> Result<bool, SolverError> {
+        context.check(report)?;
+        let kind = strat.kind();
+        let batch = self.policy == SolvePolicy::AllPerPass;
+        let changed = self.observe(kind, || {
+            let deductions = if batch { strat.deductions(board) } else { None };
+            let applied = match deductions {
+                Some(deductions) => strategy::apply_deductions(board, &deductions),
+                None => strat.apply_in(board, context),
+            };
+            // an interrupted inner search reports the progress made here
+            applied.map_err(|e| budget::rebase(e, report).in_step(kind, before))
+        })?;
+        if changed {
+            self.record(strat, board, before, report)?;
+        }
+        Ok(changed)
+    }
+
+    /// Record the step from `before` to `board` and move `before` forward.
+    /// A step beyond the step limit is undone instead.
+    fn record(
+        &self,
+        strat: &dyn Strategy,
+        board: &mut Board,
+        before: &mut Board,
+        report: &mut SolveReport,
+    ) -> Result<(), SolverError> {
+        if let Err(e) = self.budget.check_steps(report) {
+            *board = before.clone();
+            return Err(e);
+        }
+        report.record(strat.kind(), before, board);
+        *before = board.clone();
+        Ok(())
+    }
+
+    /// Run an application of the strategy of the given kind, notifying the
+    /// observers around it.
+    fn observe<F>(&self, kind: StrategyKind, apply: F) -> Result<bool, SolverError>
+    where
+        F: FnOnce() -> Result<bool, SolverError>,
+    {
+        for observer in &self.observers {
+            observer.before_apply(kind);
+        }
+        let start = Instant::now();
+        let applied = apply();
+        let elapsed = start.elapsed();
+        for observer in &self.observers {
+            match &applied {
+                Ok(changed) => observer.after_apply(kind, *changed, elapsed),
+                Err(e) => observer.apply_failed(kind, e, elapsed),
+            }
+        }
+        applied
     }
 }
 
 impl Default for Solver {
     fn default() -> Self {
-        Self::new(vec![
-            Box::new(strategy::single_candidate::SingleCandidate),
-            Box::new(strategy::hidden_single::HiddenSingle),
-            Box::new(strategy::naked_pair::NakedPair),
-            Box::new(strategy::naked_triple::NakedTriple),
-            Box::new(strategy::naked_quad::NakedQuad),
-            Box::new(strategy::hidden_pair::HiddenPair),
-            Box::new(strategy::hidden_triple::HiddenTriple),
-            Box::new(strategy::hidden_quad::HiddenQuad),
-            Box::new(strategy::pointing_pair::PointingPair),
-            Box::new(strategy::box_line_reduction::BoxLineReduction),
-            Box::new(strategy::x_wing::XWing),
-            Box::new(strategy::y_wing::YWing),
-            Box::new(strategy::xyz_wing::XYZWing),
-            Box::new(strategy::xy_wing::XYWing),
-            Box::new(strategy::xy_chain::XYChain),
-            Box::new(strategy::simple_coloring::SimpleColoring),
-            Box::new(strategy::jellyfish::Jellyfish),
-            Box::new(strategy::unique_rectangle::UniqueRectangle),
-            Box::new(strategy::swordfish::Swordfish),
-            Box::new(strategy::bug::Bug),
-            Box::new(strategy::forcing_chain::ForcingChain),
-            Box::new(strategy::nishio::Nishio),
-        ])
+        SolverBuilder::with_default_strategies().build()
     }
 }
@@ -0,0 +1,26 @@
+//! Hooks for following a solve as it happens.
+
+use crate::SolverError;
+use crate::strategy::StrategyKind;
+use std::time::Duration;
+
+/// Called around every strategy application of a [`Solver`](crate::Solver)
+/// on the board being solved. Probes on copies of the board and the inner
+/// solvers of strategies following assumptions are not observed.
+///
+/// Every call to [`SolveObserver::before_apply`] is followed by one to either
+/// [`SolveObserver::after_apply`] or [`SolveObserver::apply_failed`]. Methods
+/// take `&self`, so observers collecting data keep it behind a `Cell` or
+/// `RefCell`.
+pub trait SolveObserver {
+    /// The strategy is about to be applied.
+    fn before_apply(&self, _kind: StrategyKind) {}
+
+    /// The strategy was applied, taking `elapsed`. For a strategy applying
+    /// all of its deductions at once this covers finding and applying them.
+    fn after_apply(&self, _kind: StrategyKind, _changed: bool, _elapsed: Duration) {}
+
+    /// Applying the strategy failed after `elapsed`, ending the solve with
+    /// the error.
+    fn apply_failed(&self, _kind: StrategyKind, _error: &SolverError, _elapsed: Duration) {}
+}
@@ -0,0 +1,63 @@
+//! Order in which a solver tries its strategies.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// How a [`Solver`](crate::Solver) moves through its strategies.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SolvePolicy {
+    /// After every step start again from the first, simplest strategy, so a
+    /// harder strategy is only used when no simpler one makes progress.
+    #[default]
+    SimplestFirst,
+    /// After a step go on with the next strategy, cycling through the list
+    /// until none of them makes progress.
+    RoundRobin,
+    /// In each pass apply every strategy in turn for as long as it makes
+    /// progress, until a whole pass makes none. Strategies that can list all
+    /// of their [`Deduction`]s apply them together, each batch as one step.
+    ///
+    /// [`Deduction`]: crate::strategy::Deduction
+    AllPerPass,
+    /// Start with the singles and enable one more strategy at a time, the
+    /// first in the list that makes progress, as [`ProgressiveSolver`] does.
+    ///
+    /// [`ProgressiveSolver`]: crate::ProgressiveSolver
+    MinimalLadder,
+}
+
+impl SolvePolicy {
+    pub const ALL: [SolvePolicy; 4] = [
+        SolvePolicy::SimplestFirst,
+        SolvePolicy::RoundRobin,
+        SolvePolicy::AllPerPass,
+        SolvePolicy::MinimalLadder,
+    ];
+
+    /// Name used by profiles, such as `round-robin`.
+    pub fn name(self) -> &'static str {
+        match self {
+            SolvePolicy::SimplestFirst => "simplest-first",
+            SolvePolicy::RoundRobin => "round-robin",
+            SolvePolicy::AllPerPass => "all-per-pass",
+            SolvePolicy::MinimalLadder => "minimal-ladder",
+        }
+    }
+}
+
+impl fmt::Display for SolvePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for SolvePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SolvePolicy::ALL
+            .into_iter()
+            .find(|policy| policy.name() == s)
+            .ok_or_else(|| format!("unknown solve policy `{}`", s))
+    }
+}
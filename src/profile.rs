@@ -0,0 +1,272 @@
+//! Strategy profiles read from TOML or JSON.
+//!
+//! A profile lists the strategies a solver tries, in order. Each entry is
+//! either the name of a strategy or a table naming it and setting its
+//! parameters. Names are parsed with [`StrategyKind`]'s `FromStr`, so aliases
+//! work too:
+//!
+//! ```toml
+//! policy = "round-robin"
+//! strategies = [
+//!     "single-candidate",
+//!     "hidden-single",
+//!     { name = "xy-chain", max_length = 6 },
+//!     { name = "kraken-fish", max_size = 3, depth = "singles" },
+//!     { name = "nishio", enabled = false },
+//! ]
+//! ```
+//!
+//! The optional `policy` is the name of a [`SolvePolicy`], such as
+//! `simplest-first`, `round-robin`, `all-per-pass` or `minimal-ladder`.
+//!
+//! Parameters:
+//!
+//! - `depth`: strategies followed after an assumption by Kraken fish and the
+//!   forcing chains, one of `singles`, `basic` or `full`.
+//! - `max_size`: largest Kraken fish, from 2 (X-Wing) to 4 (Jellyfish).
+//! - `max_length`: largest number of cells in an XY-Chain.
+//! - `combine_pairs`: whether pattern overlay combines templates of two digits.
+
+use crate::SolvePolicy;
+use crate::strategy::forcing_chain::{CellForcingChain, ChainDepth};
+use crate::strategy::forcing_chain::{DynamicForcingChain, UnitForcingChain};
+use crate::strategy::kraken_fish::{self, KrakenFish};
+use crate::strategy::pattern_overlay::PatternOverlay;
+use crate::strategy::xy_chain::XYChain;
+use crate::strategy::{self, Strategy, StrategyKind};
+use serde::Deserialize;
+use std::error::Error;
+use std::path::Path;
+
+/// Errors that can occur while loading a profile.
+#[derive(Debug)]
+pub enum ProfileError {
+    /// The profile file could not be read.
+    Io(std::io::Error),
+    /// The profile is not valid TOML or JSON, or does not match the format.
+    Parse(String),
+    /// No strategy has the given name.
+    UnknownStrategy(String),
+    /// The strategy does not take the parameter.
+    UnsupportedParameter {
+        strategy: StrategyKind,
+        parameter: &'static str,
+    },
+    /// The parameter was given a value out of range.
+    InvalidParameter {
+        strategy: StrategyKind,
+        parameter: &'static str,
+        value: String,
+    },
+}
+
+impl std::fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProfileError::Io(err) => write!(f, "cannot read profile: {}", err),
+            ProfileError::Parse(msg) => write!(f, "invalid profile: {}", msg),
+            ProfileError::UnknownStrategy(name) => write!(f, "unknown strategy `{}`", name),
+            ProfileError::UnsupportedParameter {
+                strategy,
+                parameter,
+            } => write!(f, "{} has no parameter `{}`", strategy, parameter),
+            ProfileError::InvalidParameter {
+                strategy,
+                parameter,
+                value,
+            } => write!(
+                f,
+                "invalid value `{}` for parameter `{}` of {}",
+                value, parameter, strategy
+            ),
+        }
+    }
+}
+
+impl Error for ProfileError {}
+
+/// Ordered list of strategies with their parameters.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// Name of the [`SolvePolicy`], simplest first when left out.
+    pub policy: Option<String>,
+    pub strategies: Vec<StrategyEntry>,
+}
+
+/// A strategy in a profile, by name or with parameters.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum StrategyEntry {
+    Name(String),
+    Config(StrategyConfig),
+}
+
+/// A strategy in a profile with its parameters; those left out keep their
+/// defaults.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct StrategyConfig {
+    pub name: String,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    pub depth: Option<String>,
+    pub max_size: Option<usize>,
+    pub max_length: Option<usize>,
+    pub combine_pairs: Option<bool>,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+impl Profile {
+    pub fn from_toml(text: &str) -> Result<Self, ProfileError> {
+        toml::from_str(text).map_err(|err| ProfileError::Parse(err.to_string()))
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, ProfileError> {
+        serde_json::from_str(text).map_err(|err| ProfileError::Parse(err.to_string()))
+    }
+
+    /// Read a profile file, as JSON when its extension is `.json` and as TOML
+    /// otherwise.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ProfileError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(ProfileError::Io)?;
+        if path.extension().is_some_and(|ext| ext == "json") {
+            Self::from_json(&text)
+        } else {
+            Self::from_toml(&text)
+        }
+    }
+
+    pub fn policy(&self) -> Result<SolvePolicy, ProfileError> {
+        match &self.policy {
+            Some(name) => name.parse().map_err(ProfileError::Parse),
+            None => Ok(SolvePolicy::default()),
+        }
+    }
+
+    /// Create the enabled strategies in profile order.
+    pub fn strategies(&self) -> Result<Vec<Box<dyn Strategy>>, ProfileError> {
+        self.strategies
+            .iter()
+            .filter(|entry| entry.enabled())
+            .map(StrategyEntry::to_strategy)
+            .collect()
+    }
+}
+
+impl StrategyEntry {
+    fn enabled(&self) -> bool {
+        match self {
+            StrategyEntry::Name(_) => true,
+            StrategyEntry::Config(config) => config.enabled,
+        }
+    }
+
+    /// Create the strategy with the parameters of the entry.
+    pub fn to_strategy(&self) -> Result<Box<dyn Strategy>, ProfileError> {
+        match self {
+            StrategyEntry::Name(name) => Ok(strategy::kind_to_strategy(parse_kind(name)?)),
+            StrategyEntry::Config(config) => config.to_strategy(),
+        }
+    }
+}
+
+impl StrategyConfig {
+    /// Create the strategy with the parameters of the entry.
+    pub fn to_strategy(&self) -> Result<Box<dyn Strategy>, ProfileError> {
+        let kind = parse_kind(&self.name)?;
+        self.check_supported(kind)?;
+        let depth = self
+            .depth
+            .as_deref()
+            .map(|value| parse_depth(kind, value))
+            .transpose()?
+            .unwrap_or_default();
+        let strategy: Box<dyn Strategy> = match kind {
+            StrategyKind::KrakenFish => {
+                let max_size = self.max_size.unwrap_or(*kraken_fish::FISH_SIZES.end());
+                if !kraken_fish::FISH_SIZES.contains(&max_size) {
+                    return Err(invalid(kind, "max_size", max_size));
+                }
+                Box::new(KrakenFish::new(depth).with_max_size(max_size))
+            }
+            StrategyKind::CellForcingChain => Box::new(CellForcingChain::new(depth)),
+            StrategyKind::UnitForcingChain => Box::new(UnitForcingChain::new(depth)),
+            StrategyKind::DynamicForcingChain => Box::new(DynamicForcingChain::new(depth)),
+            StrategyKind::XYChain => match self.max_length {
+                // a chain needs at least two cells
+                Some(max_length) if max_length < 2 => {
+                    return Err(invalid(kind, "max_length", max_length));
+                }
+                Some(max_length) => Box::new(XYChain::with_max_length(max_length)),
+                None => Box::new(XYChain::new()),
+            },
+            StrategyKind::PatternOverlay if self.combine_pairs == Some(true) => {
+                Box::new(PatternOverlay::with_pair_combination())
+            }
+            _ => strategy::kind_to_strategy(kind),
+        };
+        Ok(strategy)
+    }
+
+    fn check_supported(&self, kind: StrategyKind) -> Result<(), ProfileError> {
+        let takes_depth = matches!(
+            kind,
+            StrategyKind::KrakenFish
+                | StrategyKind::CellForcingChain
+                | StrategyKind::UnitForcingChain
+                | StrategyKind::DynamicForcingChain
+        );
+        let given = [
+            ("depth", self.depth.is_some(), takes_depth),
+            (
+                "max_size",
+                self.max_size.is_some(),
+                kind == StrategyKind::KrakenFish,
+            ),
+            (
+                "max_length",
+                self.max_length.is_some(),
+                kind == StrategyKind::XYChain,
+            ),
+            (
+                "combine_pairs",
+                self.combine_pairs.is_some(),
+                kind == StrategyKind::PatternOverlay,
+            ),
+        ];
+        match given.iter().find(|&&(_, set, supported)| set && !supported) {
+            Some(&(parameter, _, _)) => Err(ProfileError::UnsupportedParameter {
+                strategy: kind,
+                parameter,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+fn parse_kind(name: &str) -> Result<StrategyKind, ProfileError> {
+    name.parse()
+        .map_err(|_| ProfileError::UnknownStrategy(name.to_string()))
+}
+
+fn parse_depth(kind: StrategyKind, value: &str) -> Result<ChainDepth, ProfileError> {
+    match value {
+        "singles" => Ok(ChainDepth::Singles),
+        "basic" => Ok(ChainDepth::Basic),
+        "full" => Ok(ChainDepth::Full),
+        _ => Err(invalid(kind, "depth", value)),
+    }
+}
+
+fn invalid(kind: StrategyKind, parameter: &'static str, value: impl ToString) -> ProfileError {
+    ProfileError::InvalidParameter {
+        strategy: kind,
+        parameter,
+        value: value.to_string(),
+    }
+}
@@ -1,55 +1,227 @@
 use crate::board::Board;
-use crate::strategy::{self, StrategyKind};
-use crate::{Solver, SolverError};
+use crate::budget::{self, Budget, CancelToken, SolveContext};
+use crate::report::SolveReport;
+use crate::strategy::StrategyKind;
+use crate::{SolvePolicy, Solver, SolverError};
+use std::rc::Rc;
 
-/// Solver that progressively enables more advanced strategies.
+/// Strategies enabled from the start of a minimal ladder.
+const BASIC_KINDS: [StrategyKind; 2] = [StrategyKind::SingleCandidate, StrategyKind::HiddenSingle];
+
+/// A position where the enabled strategies were stuck and the ladder had to
+/// enable a harder one.
+#[derive(Clone, Debug)]
+pub struct Bottleneck {
+    /// The board as the enabled strategies left it.
+    pub board: Board,
+    /// Number of steps taken before getting stuck.
+    pub step: usize,
+    /// Strategies not yet enabled that make progress on the board, in the
+    /// order of the ladder.
+    pub working: Vec<StrategyKind>,
+    /// The strategy enabled, the first of `working`.
+    pub simplest: StrategyKind,
+}
+
+/// Solver that progressively enables more advanced strategies, following the
+/// [`SolvePolicy::MinimalLadder`](crate::SolvePolicy::MinimalLadder) policy.
 pub struct ProgressiveSolver {
-    basic: Vec<StrategyKind>,
+    solver: Solver,
+    prune: bool,
 }
 
 impl Default for ProgressiveSolver {
     fn default() -> Self {
+        Self::from(Solver::default())
+    }
+}
+
+/// Enable the strategies of the solver in its order, starting with its
+/// singles.
+impl From<Solver> for ProgressiveSolver {
+    fn from(solver: Solver) -> Self {
         Self {
-            basic: vec![StrategyKind::SingleCandidate, StrategyKind::HiddenSingle],
+            solver,
+            prune: false,
         }
     }
 }
 
 impl ProgressiveSolver {
-    /// Solve the puzzle by enabling strategies one by one.
+    /// Stop solving once the budget runs out, counting the steps of the
+    /// whole ladder.
+    pub fn with_budget(mut self, budget: Budget) -> Self {
+        self.solver.budget = budget;
+        self
+    }
+
+    /// Stop solving once the token is cancelled.
+    pub fn with_cancel_token(mut self, token: CancelToken) -> Self {
+        self.solver.cancel = Some(token);
+        self
+    }
+
+    /// After solving, drop every enabled strategy the puzzle can be solved
+    /// without, hardest first, so [`ProgressiveSolver::solve`] only returns
+    /// strategies that are needed. A strategy enabled because it made
+    /// progress may turn out to be unnecessary once later ones are enabled.
+    pub fn with_pruning(mut self) -> Self {
+        self.prune = true;
+        self
+    }
+
+    /// Solve the puzzle by enabling strategies one by one, returning the
+    /// strategies enabled.
     pub fn solve(&self, board: &mut Board) -> Result<Vec<StrategyKind>, SolverError> {
-        let mut kinds = self.basic.clone();
-        loop {
-            Solver::new(strategies_from(&kinds)).reduce(board)?;
+        SolverError::check_valid(board)?;
+        let context = self.start();
+        let puzzle = board.clone();
+        let report = &mut SolveReport::default();
+        let mut enabled = ladder_indices(&self.solver, board, &context, report, None)?;
+        if !board.is_solved() {
+            return Err(SolverError::Unsolvable);
+        }
+        if self.prune {
+            enabled = self.prune(&puzzle, &context, enabled)?;
+        }
+        Ok(enabled
+            .iter()
+            .map(|&i| self.solver.strategies[i].kind())
+            .collect())
+    }
+
+    /// Remove the strategies the puzzle is solved without, trying the
+    /// hardest first. No single strategy can be dropped from the result, though
+    /// a smaller set of other strategies might still solve the puzzle.
+    fn prune(
+        &self,
+        puzzle: &Board,
+        context: &SolveContext,
+        mut enabled: Vec<usize>,
+    ) -> Result<Vec<usize>, SolverError> {
+        let ladder = &self.solver.strategies;
+        let mut candidates: Vec<usize> = enabled
+            .iter()
+            .copied()
+            .filter(|&i| !BASIC_KINDS.contains(&ladder[i].kind()))
+            .collect();
+        candidates.sort_by_key(|&i| std::cmp::Reverse(ladder[i].kind().difficulty()));
+        for i in candidates {
+            let rest: Vec<usize> = enabled.iter().copied().filter(|&j| j != i).collect();
+            let mut board = puzzle.clone();
+            let mut report = SolveReport::default();
+            // trials run on copies of the puzzle, which observers do not see
+            let mut trial = subset(&self.solver, &rest);
+            trial.observers.clear();
+            trial.simplest_first(&mut board, context, &mut report)?;
             if board.is_solved() {
-                return Ok(kinds);
+                enabled = rest;
             }
-            let snapshot = board.clone();
-            let mut next = None;
-            for &kind in &strategy::ALL_KINDS {
-                if kinds.contains(&kind) {
-                    continue;
-                }
-                let mut trial_board = snapshot.clone();
-                let mut trial_kinds = kinds.clone();
-                trial_kinds.push(kind);
-                Solver::new(strategies_from(&trial_kinds)).reduce(&mut trial_board)?;
-                if trial_board != snapshot {
-                    next = Some(kind);
+        }
+        Ok(enabled)
+    }
+
+    /// Reduce the puzzle as [`ProgressiveSolver::solve`] does, listing every
+    /// position where a harder strategy had to be enabled along with all the
+    /// strategies that would have made progress there. Each of them is tried
+    /// on a copy of the board, so this is slower than solving.
+    pub fn bottlenecks(&self, board: &mut Board) -> Result<Vec<Bottleneck>, SolverError> {
+        SolverError::check_valid(board)?;
+        let context = self.start();
+        let mut bottlenecks = Vec::new();
+        minimal_ladder(
+            &self.solver,
+            board,
+            &context,
+            &mut SolveReport::default(),
+            Some(&mut bottlenecks),
+        )?;
+        Ok(bottlenecks)
+    }
+
+    /// Start the clock of the whole ladder and watch its token.
+    fn start(&self) -> SolveContext {
+        let cancel = self.solver.cancel.as_ref();
+        self.solver.budget.start(cancel, &SolveContext::default())
+    }
+}
+
+/// Reduce the board with the singles of the solver, enabling its other
+/// strategies one at a time: each time the board is stuck, the first one that
+/// makes progress. Returns the strategies enabled. With `bottlenecks`, every
+/// strategy is probed each time the board is stuck and the result recorded.
+pub(crate) fn minimal_ladder(
+    solver: &Solver,
+    board: &mut Board,
+    context: &SolveContext,
+    report: &mut SolveReport,
+    bottlenecks: Option<&mut Vec<Bottleneck>>,
+) -> Result<Vec<StrategyKind>, SolverError> {
+    let enabled = ladder_indices(solver, board, context, report, bottlenecks)?;
+    Ok(enabled
+        .iter()
+        .map(|&i| solver.strategies[i].kind())
+        .collect())
+}
+
+/// [`minimal_ladder`], returning the positions of the enabled strategies in
+/// the solver.
+fn ladder_indices(
+    solver: &Solver,
+    board: &mut Board,
+    context: &SolveContext,
+    report: &mut SolveReport,
+    mut bottlenecks: Option<&mut Vec<Bottleneck>>,
+) -> Result<Vec<usize>, SolverError> {
+    let ladder = &solver.strategies;
+    let mut enabled: Vec<usize> = (0..ladder.len())
+        .filter(|&i| BASIC_KINDS.contains(&ladder[i].kind()))
+        .collect();
+    loop {
+        subset(solver, &enabled).simplest_first(board, context, report)?;
+        if board.is_solved() {
+            break;
+        }
+        // the enabled strategies are stuck, so any change comes from the new one
+        let mut working = Vec::new();
+        for i in (0..ladder.len()).filter(|i| !enabled.contains(i)) {
+            context.check(report)?;
+            let probe = ladder[i]
+                .apply_in(&mut board.clone(), context)
+                .map_err(|e| budget::rebase(e, report).in_step(ladder[i].kind(), board))?;
+            if probe {
+                working.push(i);
+                if bottlenecks.is_none() {
                     break;
                 }
             }
-            match next {
-                Some(k) => kinds.push(k),
-                None => return Err(SolverError::Unsolvable),
-            }
         }
+        let Some(&next) = working.first() else {
+            break;
+        };
+        if let Some(bottlenecks) = bottlenecks.as_deref_mut() {
+            bottlenecks.push(Bottleneck {
+                board: board.clone(),
+                step: report.steps,
+                working: working.iter().map(|&i| ladder[i].kind()).collect(),
+                simplest: ladder[next].kind(),
+            });
+        }
+        enabled.push(next);
     }
+    Ok(enabled)
 }
 
-fn strategies_from(kinds: &[StrategyKind]) -> Vec<Box<dyn strategy::Strategy>> {
-    kinds
-        .iter()
-        .map(|&k| strategy::kind_to_strategy(k))
-        .collect()
+/// The strategies of the solver at the given positions, simplest first.
+fn subset(solver: &Solver, indices: &[usize]) -> Solver {
+    Solver {
+        strategies: indices
+            .iter()
+            .map(|&i| Rc::clone(&solver.strategies[i]))
+            .collect(),
+        policy: SolvePolicy::SimplestFirst,
+        budget: solver.budget,
+        cancel: solver.cancel.clone(),
+        observers: solver.observers.clone(),
+    }
 }
@@ -0,0 +1,130 @@
+//! Per-step record of a solver run.
+
+use crate::board::{Board, Digit};
+use crate::strategy::StrategyKind;
+
+/// How one strategy kind contributed to a solver run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StrategyUsage {
+    pub kind: StrategyKind,
+    /// Number of steps taken with the strategy.
+    pub applications: usize,
+    /// Values placed by those steps.
+    pub placements: usize,
+    /// Candidates removed by those steps, not counting those ruled out by
+    /// the values placed in the same step.
+    pub eliminations: usize,
+    /// Index of the first step taken with the strategy.
+    pub first_step: usize,
+    /// Unsolved cells left when the strategy was first needed.
+    pub unsolved_at_first_use: usize,
+}
+
+/// Cell and digit of a placement or elimination.
+pub type Change = (usize, usize, Digit);
+
+/// Values placed and candidates removed by one step. A step applying a batch
+/// of deductions groups all of them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SolveStep {
+    pub kind: StrategyKind,
+    pub placements: Vec<Change>,
+    /// Candidates removed, not counting those ruled out by the values placed
+    /// in the same step.
+    pub eliminations: Vec<Change>,
+}
+
+/// Record of every step a solver took.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SolveReport {
+    /// Strategies in the order they were first needed.
+    pub usages: Vec<StrategyUsage>,
+    /// Total number of steps taken.
+    pub steps: usize,
+    /// Index of the first step taken with the hardest strategy used, by the
+    /// canonical difficulty order.
+    pub hardest_step: Option<usize>,
+    /// Every step in the order taken.
+    pub trace: Vec<SolveStep>,
+}
+
+impl SolveReport {
+    /// Kinds in the order they were first needed.
+    pub fn kinds(&self) -> Vec<StrategyKind> {
+        self.usages.iter().map(|usage| usage.kind).collect()
+    }
+
+    pub fn usage(&self, kind: StrategyKind) -> Option<&StrategyUsage> {
+        self.usages.iter().find(|usage| usage.kind == kind)
+    }
+
+    /// The hardest strategy used, by the canonical difficulty order.
+    pub fn hardest(&self) -> Option<StrategyKind> {
+        self.usages
+            .iter()
+            .map(|usage| usage.kind)
+            .max_by_key(|kind| kind.difficulty())
+    }
+
+    /// Record a step from `before` to `after` taken with the strategy of the
+    /// given kind.
+    pub(crate) fn record(&mut self, kind: StrategyKind, before: &Board, after: &Board) {
+        let (placements, eliminations) = step_changes(before, after);
+        let step = self.steps;
+        self.steps += 1;
+        if self
+            .hardest()
+            .is_none_or(|hardest| kind.difficulty() > hardest.difficulty())
+        {
+            self.hardest_step = Some(step);
+        }
+        let index = match self.usages.iter().position(|usage| usage.kind == kind) {
+            Some(index) => index,
+            None => {
+                self.usages.push(StrategyUsage {
+                    kind,
+                    applications: 0,
+                    placements: 0,
+                    eliminations: 0,
+                    first_step: step,
+                    unsolved_at_first_use: before.unsolved_cells().count(),
+                });
+                self.usages.len() - 1
+            }
+        };
+        let usage = &mut self.usages[index];
+        usage.applications += 1;
+        usage.placements += placements.len();
+        usage.eliminations += eliminations.len();
+        self.trace.push(SolveStep {
+            kind,
+            placements,
+            eliminations,
+        });
+    }
+}
+
+/// Values placed and candidates removed going from `before` to `after`.
+fn step_changes(before: &Board, after: &Board) -> (Vec<Change>, Vec<Change>) {
+    let placed: Vec<_> = before
+        .unsolved_cells()
+        .filter_map(|(r, c)| after.get(r, c).map(|d| (r, c, d)))
+        .collect();
+    let eliminations = after
+        .unsolved_cells()
+        .flat_map(|(r, c)| {
+            before
+                .candidates(r, c)
+                .difference(after.candidates(r, c))
+                .iter()
+                .filter(|&d| {
+                    !placed
+                        .iter()
+                        .any(|&(pr, pc, pd)| pd == d && after.sees((r, c), (pr, pc)))
+                })
+                .map(move |d| (r, c, d))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    (placed, eliminations)
+}
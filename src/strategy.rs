@@ -1,5 +1,6 @@
 use crate::SolverError;
-use crate::board::Board;
+use crate::board::{Board, CandidateSet, Digit, Unit};
+use crate::budget::SolveContext;
 
 pub mod basic {
     pub mod box_line_reduction;
@@ -7,6 +8,7 @@ pub mod basic {
     pub mod hidden_quad;
     pub mod hidden_single;
     pub mod hidden_triple;
+    pub mod locked_candidates;
     pub mod naked_pair;
     pub mod naked_quad;
     pub mod naked_triple;
@@ -15,12 +17,23 @@ pub mod basic {
 }
 
 pub mod advanced {
+    pub mod aligned_exclusion;
+    pub(crate) mod als;
     pub mod bug;
+    pub(crate) mod coloring;
+    pub mod death_blossom;
+    pub mod exocet;
     pub mod forcing_chain;
     pub mod jellyfish;
+    pub mod kraken_fish;
+    pub mod multi_coloring;
+    pub mod multi_sector_locked_set;
     pub mod nishio;
+    pub mod pattern_overlay;
     pub mod simple_coloring;
+    pub mod sue_de_coq;
     pub mod swordfish;
+    pub mod three_d_medusa;
     pub mod unique_rectangle;
     pub mod x_wing;
     pub mod xy_chain;
@@ -29,8 +42,11 @@ pub mod advanced {
     pub mod y_wing;
 }
 
+mod metadata;
+
 pub use advanced::*;
 pub use basic::*;
+pub use metadata::{ParseStrategyKindError, StrategyFamily, StrategyInfo};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum StrategyKind {
@@ -42,29 +58,178 @@ pub enum StrategyKind {
     HiddenPair,
     HiddenTriple,
     HiddenQuad,
-    PointingPair,
-    BoxLineReduction,
+    LockedCandidatesType1,
+    LockedCandidatesType2,
     XWing,
     YWing,
     XYZWing,
     XYWing,
+    SueDeCoq,
+    AlignedPairExclusion,
+    AlignedTripleExclusion,
     XYChain,
     SimpleColoring,
+    MultiColoring,
+    ThreeDMedusa,
     Jellyfish,
     UniqueRectangle,
+    UniqueRectangleType2,
+    UniqueRectangleType3,
+    UniqueRectangleType4,
+    UniqueRectangleType5,
+    UniqueRectangleType6,
+    HiddenRectangle,
+    AvoidableRectangle,
     Swordfish,
     Bug,
-    ForcingChain,
+    DeathBlossom,
+    JuniorExocet,
+    Exocet,
+    SkLoop,
+    MultiSectorLockedSet,
     Nishio,
+    PatternOverlay,
+    KrakenFish,
+    CellForcingChain,
+    UnitForcingChain,
+    DynamicForcingChain,
+}
+
+/// A single deduction found by a strategy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Deduction {
+    Place(usize, usize, Digit),
+    Eliminate(usize, usize, Digit),
 }
 
 pub trait Strategy {
     fn kind(&self) -> StrategyKind;
     fn apply(&self, board: &mut Board) -> Result<bool, SolverError>;
+
+    /// [`Strategy::apply`] as a step of the solve running in the context.
+    /// Strategies that search for a long time between steps, or follow
+    /// assumptions with an inner solver, stop when the context does.
+    fn apply_in(&self, board: &mut Board, _context: &SolveContext) -> Result<bool, SolverError> {
+        self.apply(board)
+    }
+
+    /// Every deduction the strategy finds on the board as it is, so they can
+    /// be applied together as one step. `None` when the strategy only takes
+    /// one step at a time through [`Strategy::apply`].
+    fn deductions(&self, _board: &Board) -> Option<Vec<Deduction>> {
+        None
+    }
+}
+
+/// Apply the deductions in order, reporting whether any of them changed the
+/// board. Placing a digit that is no longer a candidate of its cell, for
+/// instance because an earlier deduction placed it in a peer, is a
+/// contradiction.
+pub fn apply_deductions(board: &mut Board, deductions: &[Deduction]) -> Result<bool, SolverError> {
+    deductions
+        .iter()
+        .try_fold(false, |changed, &deduction| match deduction {
+            Deduction::Place(r, c, d) => match board.get(r, c) {
+                Some(value) if value == d => Ok(changed),
+                None if board.candidates(r, c).contains(d) => {
+                    board.set(r, c, d);
+                    Ok(true)
+                }
+                _ => Err(SolverError::contradiction(r, c, Some(d))),
+            },
+            Deduction::Eliminate(r, c, d) => {
+                Ok(eliminate_candidates(board, [(r, c, d)])? || changed)
+            }
+        })
+}
+
+/// Remove every listed candidate from the board, reporting whether any of
+/// them was still present.
+pub(crate) fn eliminate_candidates<I>(board: &mut Board, candidates: I) -> Result<bool, SolverError>
+where
+    I: IntoIterator<Item = (usize, usize, Digit)>,
+{
+    eliminate_candidates_in(board, None, &[], candidates)
+}
+
+/// [`eliminate_candidates`] for candidates ruled out by the given cells,
+/// which lie in the unit when there is one. A cell left without candidates
+/// is reported along with them.
+pub(crate) fn eliminate_candidates_in<I>(
+    board: &mut Board,
+    unit: Option<Unit>,
+    cells: &[(usize, usize)],
+    candidates: I,
+) -> Result<bool, SolverError>
+where
+    I: IntoIterator<Item = (usize, usize, Digit)>,
+{
+    candidates
+        .into_iter()
+        .try_fold(false, |changed, (r, c, d)| {
+            match board.eliminate_candidate(r, c, d) {
+                Some(true) => Ok(true),
+                Some(false) => Ok(changed),
+                None => {
+                    Err(SolverError::contradiction(r, c, Some(d)).in_unit(unit, cells.to_vec()))
+                }
+            }
+        })
+}
+
+/// Subsets of `items` with `min` to `max` elements, keeping the order of the
+/// items.
+pub(crate) fn subsets<T: Copy>(items: &[T], min: usize, max: usize) -> Vec<Vec<T>> {
+    subsets_where(items, min, max, |_| true)
+}
+
+/// Subsets of `items` with `min` to `max` elements that `keep` accepts. A
+/// subset `keep` turns down is not extended any further, so it should only
+/// turn down subsets whose supersets fail too.
+pub(crate) fn subsets_where<T, F>(items: &[T], min: usize, max: usize, keep: F) -> Vec<Vec<T>>
+where
+    T: Copy,
+    F: Fn(&[T]) -> bool,
+{
+    fn extend<T: Copy>(
+        items: &[T],
+        start: usize,
+        chosen: &mut Vec<T>,
+        (min, max): (usize, usize),
+        keep: &dyn Fn(&[T]) -> bool,
+        found: &mut Vec<Vec<T>>,
+    ) {
+        if chosen.len() >= min {
+            found.push(chosen.clone());
+        }
+        if chosen.len() == max {
+            return;
+        }
+        for (idx, &item) in items.iter().enumerate().skip(start) {
+            chosen.push(item);
+            if keep(chosen) {
+                extend(items, idx + 1, chosen, (min, max), keep, found);
+            }
+            chosen.pop();
+        }
+    }
+    let mut found = Vec::new();
+    extend(items, 0, &mut Vec::new(), (min, max), &keep, &mut found);
+    found
+}
+
+/// Every candidate of the cells.
+pub(crate) fn union_of(cells: &[((usize, usize), CandidateSet)]) -> CandidateSet {
+    cells
+        .iter()
+        .fold(CandidateSet::empty(), |acc, &(_, cands)| acc.union(cands))
 }
 
 /// All strategies in order from simplest to most advanced.
-pub const ALL_KINDS: [StrategyKind; 22] = [
+///
+/// This is the canonical difficulty order: default solvers try strategies in
+/// it and reports rank steps by it.
+pub const ALL_KINDS: [StrategyKind; 43] = [
     StrategyKind::SingleCandidate,
     StrategyKind::HiddenSingle,
     StrategyKind::NakedPair,
@@ -73,22 +238,53 @@ pub const ALL_KINDS: [StrategyKind; 22] = [
     StrategyKind::HiddenPair,
     StrategyKind::HiddenTriple,
     StrategyKind::HiddenQuad,
-    StrategyKind::PointingPair,
-    StrategyKind::BoxLineReduction,
+    StrategyKind::LockedCandidatesType1,
+    StrategyKind::LockedCandidatesType2,
     StrategyKind::XWing,
     StrategyKind::YWing,
+    StrategyKind::XYZWing,
+    StrategyKind::XYWing,
     StrategyKind::Swordfish,
-    StrategyKind::Jellyfish,
     StrategyKind::UniqueRectangle,
-    StrategyKind::XYZWing,
+    StrategyKind::UniqueRectangleType2,
+    StrategyKind::UniqueRectangleType3,
+    StrategyKind::UniqueRectangleType4,
+    StrategyKind::UniqueRectangleType5,
+    StrategyKind::UniqueRectangleType6,
+    StrategyKind::HiddenRectangle,
+    StrategyKind::AvoidableRectangle,
+    StrategyKind::Jellyfish,
+    StrategyKind::Bug,
+    StrategyKind::SueDeCoq,
+    StrategyKind::AlignedPairExclusion,
+    StrategyKind::AlignedTripleExclusion,
     StrategyKind::XYChain,
-    StrategyKind::XYWing,
     StrategyKind::SimpleColoring,
-    StrategyKind::Bug,
-    StrategyKind::ForcingChain,
+    StrategyKind::MultiColoring,
+    StrategyKind::ThreeDMedusa,
+    StrategyKind::DeathBlossom,
+    StrategyKind::JuniorExocet,
+    StrategyKind::Exocet,
+    StrategyKind::SkLoop,
+    StrategyKind::MultiSectorLockedSet,
+    StrategyKind::PatternOverlay,
     StrategyKind::Nishio,
+    StrategyKind::KrakenFish,
+    StrategyKind::CellForcingChain,
+    StrategyKind::UnitForcingChain,
+    StrategyKind::DynamicForcingChain,
 ];
 
+impl StrategyKind {
+    /// Position in the canonical difficulty order, 0 being the simplest.
+    pub fn difficulty(self) -> usize {
+        ALL_KINDS
+            .iter()
+            .position(|&kind| kind == self)
+            .expect("every kind is listed in ALL_KINDS")
+    }
+}
+
 /// Create a boxed strategy instance for the given kind.
 pub fn kind_to_strategy(kind: StrategyKind) -> Box<dyn Strategy> {
     match kind {
@@ -100,19 +296,44 @@ pub fn kind_to_strategy(kind: StrategyKind) -> Box<dyn Strategy> {
         StrategyKind::HiddenPair => Box::new(hidden_pair::HiddenPair),
         StrategyKind::HiddenTriple => Box::new(hidden_triple::HiddenTriple),
         StrategyKind::HiddenQuad => Box::new(hidden_quad::HiddenQuad),
-        StrategyKind::PointingPair => Box::new(pointing_pair::PointingPair),
-        StrategyKind::BoxLineReduction => Box::new(box_line_reduction::BoxLineReduction),
+        StrategyKind::LockedCandidatesType1 => Box::new(locked_candidates::LockedCandidatesType1),
+        StrategyKind::LockedCandidatesType2 => Box::new(locked_candidates::LockedCandidatesType2),
         StrategyKind::XWing => Box::new(x_wing::XWing),
         StrategyKind::YWing => Box::new(y_wing::YWing),
         StrategyKind::Swordfish => Box::new(swordfish::Swordfish),
         StrategyKind::Jellyfish => Box::new(jellyfish::Jellyfish),
         StrategyKind::UniqueRectangle => Box::new(unique_rectangle::UniqueRectangle),
+        StrategyKind::UniqueRectangleType2 => Box::new(unique_rectangle::UniqueRectangleType2),
+        StrategyKind::UniqueRectangleType3 => Box::new(unique_rectangle::UniqueRectangleType3),
+        StrategyKind::UniqueRectangleType4 => Box::new(unique_rectangle::UniqueRectangleType4),
+        StrategyKind::UniqueRectangleType5 => Box::new(unique_rectangle::UniqueRectangleType5),
+        StrategyKind::UniqueRectangleType6 => Box::new(unique_rectangle::UniqueRectangleType6),
+        StrategyKind::HiddenRectangle => Box::new(unique_rectangle::HiddenRectangle),
+        StrategyKind::AvoidableRectangle => Box::new(unique_rectangle::AvoidableRectangle),
         StrategyKind::XYZWing => Box::new(xyz_wing::XYZWing),
-        StrategyKind::XYChain => Box::new(xy_chain::XYChain),
+        StrategyKind::XYChain => Box::new(xy_chain::XYChain::new()),
         StrategyKind::XYWing => Box::new(xy_wing::XYWing),
+        StrategyKind::SueDeCoq => Box::new(sue_de_coq::SueDeCoq),
+        StrategyKind::AlignedPairExclusion => Box::new(aligned_exclusion::AlignedPairExclusion),
+        StrategyKind::AlignedTripleExclusion => Box::new(aligned_exclusion::AlignedTripleExclusion),
         StrategyKind::SimpleColoring => Box::new(simple_coloring::SimpleColoring),
+        StrategyKind::MultiColoring => Box::new(multi_coloring::MultiColoring),
+        StrategyKind::ThreeDMedusa => Box::new(three_d_medusa::ThreeDMedusa),
         StrategyKind::Bug => Box::new(bug::Bug),
-        StrategyKind::ForcingChain => Box::new(forcing_chain::ForcingChain),
+        StrategyKind::DeathBlossom => Box::new(death_blossom::DeathBlossom),
+        StrategyKind::JuniorExocet => Box::new(exocet::JuniorExocet),
+        StrategyKind::Exocet => Box::new(exocet::Exocet),
+        StrategyKind::SkLoop => Box::new(multi_sector_locked_set::SkLoop),
+        StrategyKind::MultiSectorLockedSet => {
+            Box::new(multi_sector_locked_set::MultiSectorLockedSet)
+        }
         StrategyKind::Nishio => Box::new(nishio::Nishio),
+        StrategyKind::PatternOverlay => Box::new(pattern_overlay::PatternOverlay::new()),
+        StrategyKind::KrakenFish => Box::new(kraken_fish::KrakenFish::default()),
+        StrategyKind::CellForcingChain => Box::new(forcing_chain::CellForcingChain::default()),
+        StrategyKind::UnitForcingChain => Box::new(forcing_chain::UnitForcingChain::default()),
+        StrategyKind::DynamicForcingChain => {
+            Box::new(forcing_chain::DynamicForcingChain::default())
+        }
     }
 }
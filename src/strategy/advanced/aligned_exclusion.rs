@@ -0,0 +1,147 @@
+use super::als::{Als, find_als};
+use crate::SolverError;
+use crate::board::{Board, Digit};
+use crate::strategy::{Strategy, StrategyKind, eliminate_candidates, subsets_where};
+
+/// Largest number of candidates in a base cell.
+const MAX_BASE_CANDIDATES: usize = 4;
+/// Largest almost locked set used to exclude combinations.
+const MAX_ALS_CELLS: usize = 3;
+
+type Coord = (usize, usize);
+
+/// Aligned Pair Exclusion: two cells seeing each other.
+///
+/// Both aligned exclusions only take base cells that all see each other.
+/// The general technique also accepts base cells that do not, excluding
+/// combinations through almost locked sets alone; that is left out on
+/// purpose, since the number of groups to try grows far faster than the
+/// eliminations it adds.
+pub struct AlignedPairExclusion;
+
+impl Strategy for AlignedPairExclusion {
+    fn kind(&self) -> StrategyKind {
+        StrategyKind::AlignedPairExclusion
+    }
+
+    fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
+        apply_aligned(board, 2)
+    }
+}
+
+/// Aligned Triple Exclusion: three cells seeing each other.
+pub struct AlignedTripleExclusion;
+
+impl Strategy for AlignedTripleExclusion {
+    fn kind(&self) -> StrategyKind {
+        StrategyKind::AlignedTripleExclusion
+    }
+
+    fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
+        apply_aligned(board, 3)
+    }
+}
+
+/// Every combination of values for the base cells is tried. A combination
+/// is excluded when two base cells seeing each other get the same digit, or
+/// when it removes two digits from an almost locked set, leaving its cells
+/// with fewer digits than cells; a bivalue cell is the smallest such set. A
+/// candidate used by no remaining combination is removed.
+fn apply_aligned(board: &mut Board, size: usize) -> Result<bool, SolverError> {
+    let sets = find_als(board, MAX_ALS_CELLS);
+    let cells: Vec<Coord> = board
+        .unsolved_cells()
+        .filter(|&(r, c)| (2..=MAX_BASE_CANDIDATES).contains(&board.candidates(r, c).len()))
+        .collect();
+    for base in aligned_groups(board, &cells, size) {
+        let excluders: Vec<&Als> = sets
+            .iter()
+            .filter(|als| als.cells.iter().all(|cell| !base.contains(cell)))
+            .collect();
+        let found = exclusions(board, &base, &excluders);
+        if eliminate_candidates(board, found)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Groups of `size` cells that all see each other.
+fn aligned_groups(board: &Board, cells: &[Coord], size: usize) -> Vec<Vec<Coord>> {
+    // the other pairs were checked before the last cell was added
+    subsets_where(cells, size, size, |set| {
+        set.split_last()
+            .is_none_or(|(&last, rest)| rest.iter().all(|&other| board.sees(last, other)))
+    })
+}
+
+fn exclusions(board: &Board, base: &[Coord], excluders: &[&Als]) -> Vec<(usize, usize, Digit)> {
+    let mut allowed = vec![Vec::new(); base.len()];
+    let mut values = Vec::with_capacity(base.len());
+    collect_allowed(board, base, excluders, &mut values, &mut allowed);
+    base.iter()
+        .zip(&allowed)
+        .flat_map(|(&(r, c), kept)| {
+            board
+                .candidates(r, c)
+                .iter()
+                .filter(|d| !kept.contains(d))
+                .map(move |d| (r, c, d))
+        })
+        .collect()
+}
+
+/// Enumerate the combinations and record the digits of every valid one.
+fn collect_allowed(
+    board: &Board,
+    base: &[Coord],
+    excluders: &[&Als],
+    values: &mut Vec<Digit>,
+    allowed: &mut [Vec<Digit>],
+) {
+    let Some(&cell) = base.get(values.len()) else {
+        if !excluded(board, base, values, excluders) {
+            for (kept, &d) in allowed.iter_mut().zip(values.iter()) {
+                if !kept.contains(&d) {
+                    kept.push(d);
+                }
+            }
+        }
+        return;
+    };
+    for d in board.candidates(cell.0, cell.1).iter() {
+        values.push(d);
+        collect_allowed(board, base, excluders, values, allowed);
+        values.pop();
+    }
+}
+
+fn excluded(board: &Board, base: &[Coord], values: &[Digit], excluders: &[&Als]) -> bool {
+    let clash = base.iter().zip(values).enumerate().any(|(i, (&a, da))| {
+        base.iter()
+            .zip(values)
+            .skip(i + 1)
+            .any(|(&b, db)| da == db && board.sees(a, b))
+    });
+    clash
+        || excluders.iter().any(|als| {
+            let removed = als
+                .digits
+                .iter()
+                .filter(|&d| {
+                    let holders: Vec<Coord> = base
+                        .iter()
+                        .zip(values)
+                        .filter(|&(_, &v)| v == d)
+                        .map(|(&cell, _)| cell)
+                        .collect();
+                    !holders.is_empty()
+                        && als
+                            .cells_with(board, d)
+                            .iter()
+                            .all(|&cell| holders.iter().any(|&h| board.sees(cell, h)))
+                })
+                .count();
+            removed >= 2
+        })
+}
@@ -0,0 +1,47 @@
+//! Almost locked sets: `n` unsolved cells of one unit holding `n + 1` digits.
+
+use crate::board::{Board, CandidateSet, Digit, Unit};
+use crate::strategy::{subsets_where, union_of};
+use std::collections::HashSet;
+
+type Coord = (usize, usize);
+
+pub(crate) struct Als {
+    pub cells: Vec<Coord>,
+    pub digits: CandidateSet,
+}
+
+impl Als {
+    /// Cells of the set that still hold the digit.
+    pub fn cells_with(&self, board: &Board, digit: Digit) -> Vec<Coord> {
+        self.cells
+            .iter()
+            .copied()
+            .filter(|&(r, c)| board.candidates(r, c).contains(digit))
+            .collect()
+    }
+}
+
+/// Find every almost locked set of up to `max_cells` cells. Sets lying in
+/// two units at once are only reported once.
+pub(crate) fn find_als(board: &Board, max_cells: usize) -> Vec<Als> {
+    let mut seen: HashSet<Vec<Coord>> = HashSet::new();
+    let mut found = Vec::new();
+    for unit in Unit::all() {
+        let cells: Vec<(Coord, CandidateSet)> = board.unsolved_in_unit(unit).collect();
+        let sets = subsets_where(&cells, 1, max_cells, |set| {
+            union_of(set).len() <= max_cells + 1
+        });
+        for set in sets {
+            let digits = union_of(&set);
+            let coords: Vec<Coord> = set.into_iter().map(|(coord, _)| coord).collect();
+            if digits.len() == coords.len() + 1 && seen.insert(coords.clone()) {
+                found.push(Als {
+                    cells: coords,
+                    digits,
+                });
+            }
+        }
+    }
+    found
+}
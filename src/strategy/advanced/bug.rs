@@ -1,7 +1,21 @@
 use crate::SolverError;
-use crate::board::{self, Board};
-use crate::strategy::{Strategy, StrategyKind};
+use crate::board::{Board, CandidateSet, Digit, Unit};
+use crate::strategy::{Deduction, Strategy, StrategyKind, apply_deductions};
+use std::collections::HashMap;
 
+const PAIR_LEN: usize = 2;
+/// Largest number of cells with more than two candidates that is searched.
+const MAX_POLY_CELLS: usize = 4;
+
+type Coord = (usize, usize);
+
+/// Bivalue Universal Grave (BUG+n).
+///
+/// If every unsolved cell kept only two candidates and every digit appeared
+/// exactly twice in each unit where it is unsolved, the puzzle would not have
+/// a unique solution. The candidates that have to be removed from the
+/// poly-valued cells to reach that state are the true extra candidates, and
+/// at least one of them must be placed.
 pub struct Bug;
 
 impl Strategy for Bug {
@@ -10,48 +24,106 @@ impl Strategy for Bug {
     }
 
     fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
-        let mut multi_cell = None;
+        let mut reduced: HashMap<Coord, CandidateSet> = HashMap::new();
+        let mut poly = Vec::new();
         for (r, c) in board.unsolved_cells() {
-            let count = board.candidates(r, c).len();
-            if count > 2 {
-                if multi_cell.is_none() {
-                    multi_cell = Some((r, c));
-                } else {
-                    return Ok(false);
-                }
-            } else if count < 2 {
-                return Ok(false);
+            let cands = board.candidates(r, c);
+            match cands.len() {
+                0 | 1 => return Ok(false),
+                PAIR_LEN => {}
+                _ => poly.push(((r, c), cands)),
             }
+            reduced.insert((r, c), cands);
+        }
+        if poly.is_empty() || poly.len() > MAX_POLY_CELLS {
+            return Ok(false);
         }
 
-        let (r, c) = match multi_cell {
-            Some(pos) => pos,
-            None => return Ok(false),
-        };
+        let mut extras = Vec::new();
+        match search(board, &poly, &mut reduced, &mut extras) {
+            Some(deductions) => apply_deductions(board, &deductions),
+            None => Ok(false),
+        }
+    }
+}
 
-        let mut digit_counts = [0usize; 10];
-        for (r0, c0) in board.unsolved_cells() {
-            for d in board.candidates(r0, c0) {
-                digit_counts[d as usize] += 1;
+/// Choose the two candidates each poly-valued cell keeps in the BUG pattern
+/// and return the first deductions one of those choices allows.
+fn search(
+    board: &Board,
+    poly: &[(Coord, CandidateSet)],
+    reduced: &mut HashMap<Coord, CandidateSet>,
+    extras: &mut Vec<(usize, usize, Digit)>,
+) -> Option<Vec<Deduction>> {
+    let Some((&(coord, cands), rest)) = poly.split_first() else {
+        return if is_bug_pattern(board, reduced) {
+            deduce(board, extras)
+        } else {
+            None
+        };
+    };
+    let digits: Vec<Digit> = cands.iter().collect();
+    for (i, &a) in digits.iter().enumerate() {
+        for &b in &digits[i + 1..] {
+            let mut pair = CandidateSet::empty();
+            pair.insert(a);
+            pair.insert(b);
+            reduced.insert(coord, pair);
+            let before = extras.len();
+            extras.extend(cands.difference(pair).iter().map(|d| (coord.0, coord.1, d)));
+            let found = search(board, rest, reduced, extras);
+            extras.truncate(before);
+            if found.is_some() {
+                return found;
             }
         }
+    }
+    reduced.insert(coord, cands);
+    None
+}
 
-        let mut choice = None;
-        for d in board.candidates(r, c) {
-            if digit_counts[d as usize] == 3 {
-                if choice.is_none() {
-                    choice = Some(d);
-                } else {
-                    return Ok(false);
+/// Every digit must appear exactly twice, or not at all, in each unit.
+fn is_bug_pattern(board: &Board, reduced: &HashMap<Coord, CandidateSet>) -> bool {
+    Unit::all().all(|unit| {
+        let mut counts = [0usize; 10];
+        for coord in board.unit_iter(unit) {
+            if let Some(cands) = reduced.get(&coord) {
+                for d in cands.iter() {
+                    counts[d as usize] += 1;
                 }
             }
         }
+        counts.iter().all(|&n| n == 0 || n == PAIR_LEN)
+    })
+}
 
-        if let Some(d) = choice {
-            board.set(r, c, d);
-            return Ok(true);
+/// One of the extra candidates is true: when they share a cell that cell is
+/// limited to them, and when they share a digit the digit leaves every cell
+/// seeing all of them.
+fn deduce(board: &Board, extras: &[(usize, usize, Digit)]) -> Option<Vec<Deduction>> {
+    let &(r, c, d) = extras.first()?;
+    if extras.iter().all(|&(er, ec, _)| (er, ec) == (r, c)) {
+        if extras.len() == 1 {
+            return Some(vec![Deduction::Place(r, c, d)]);
         }
-
-        Ok(false)
+        let keep: Vec<Digit> = extras.iter().map(|&(_, _, ed)| ed).collect();
+        let found: Vec<_> = board
+            .candidates(r, c)
+            .iter()
+            .filter(|cd| !keep.contains(cd))
+            .map(|cd| Deduction::Eliminate(r, c, cd))
+            .collect();
+        return (!found.is_empty()).then_some(found);
+    }
+    if extras.iter().all(|&(_, _, ed)| ed == d) {
+        let cells: Vec<Coord> = extras.iter().map(|&(er, ec, _)| (er, ec)).collect();
+        let found: Vec<_> = board
+            .common_peers(&cells)
+            .into_iter()
+            .filter(|&(pr, pc)| board.candidates(pr, pc).contains(d))
+            .map(|(pr, pc)| Deduction::Eliminate(pr, pc, d))
+            .collect();
+        return (!found.is_empty()).then_some(found);
     }
+    None
 }
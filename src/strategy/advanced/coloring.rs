@@ -0,0 +1,73 @@
+//! Shared helpers for the coloring based strategies.
+
+use crate::board::{Board, Digit, Unit};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// Graph of strong links between nodes.
+pub(crate) type Adjacency<N> = HashMap<N, Vec<N>>;
+
+/// A connected component with each node assigned one of two colors.
+pub(crate) struct Component<N> {
+    pub colors: HashMap<N, bool>,
+}
+
+impl<N: Copy + Eq + Hash> Component<N> {
+    /// Split the component into the nodes of each color, indexed by the color.
+    pub fn color_sets(&self) -> [HashSet<N>; 2] {
+        let mut sets = [HashSet::new(), HashSet::new()];
+        for (&node, &color) in &self.colors {
+            sets[color as usize].insert(node);
+        }
+        sets
+    }
+}
+
+/// Add an undirected strong link between two nodes.
+pub(crate) fn link<N: Copy + Eq + Hash>(adjacency: &mut Adjacency<N>, a: N, b: N) {
+    adjacency.entry(a).or_default().push(b);
+    adjacency.entry(b).or_default().push(a);
+}
+
+/// Build the conjugate pair graph of a digit: cells linked when they are the
+/// only two candidates for the digit in a row, column or box.
+pub(crate) fn conjugate_pairs(board: &Board, digit: Digit) -> Adjacency<(usize, usize)> {
+    let mut adjacency = Adjacency::new();
+    for unit in Unit::all() {
+        let coords = board.candidate_coords(unit, digit);
+        if coords.len() == 2 {
+            let mut it = coords.iter();
+            let first = it.next().unwrap();
+            let second = it.next().unwrap();
+            link(&mut adjacency, first, second);
+        }
+    }
+    adjacency
+}
+
+/// Two-color every connected component of the graph using a breadth first
+/// search so that strongly linked nodes always get opposite colors.
+pub(crate) fn color_components<N: Copy + Eq + Hash>(adjacency: &Adjacency<N>) -> Vec<Component<N>> {
+    let mut visited: HashSet<N> = HashSet::new();
+    let mut components = Vec::new();
+    for &start in adjacency.keys() {
+        if !visited.insert(start) {
+            continue;
+        }
+        let mut colors = HashMap::new();
+        let mut queue = VecDeque::new();
+        colors.insert(start, false);
+        queue.push_back(start);
+        while let Some(node) = queue.pop_front() {
+            let color = colors[&node];
+            for &next in adjacency.get(&node).into_iter().flatten() {
+                if visited.insert(next) {
+                    colors.insert(next, !color);
+                    queue.push_back(next);
+                }
+            }
+        }
+        components.push(Component { colors });
+    }
+    components
+}
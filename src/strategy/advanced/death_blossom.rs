@@ -0,0 +1,124 @@
+use super::als::{Als, find_als};
+use crate::SolverError;
+use crate::board::{self, Board, Digit};
+use crate::strategy::{Strategy, StrategyKind, eliminate_candidates};
+
+/// Largest almost locked set used as a petal.
+const MAX_PETAL_CELLS: usize = 4;
+/// Largest number of stem candidates, one petal is needed for each.
+const MAX_STEM_CANDIDATES: usize = 3;
+
+type Coord = (usize, usize);
+
+/// Death Blossom: every candidate of a stem cell is linked to its own petal,
+/// an almost locked set whose cells holding that candidate all see the stem.
+/// Whatever the stem becomes, one petal turns into a locked set, so a digit
+/// common to all petals is removed from cells seeing all its petal positions.
+pub struct DeathBlossom;
+
+impl Strategy for DeathBlossom {
+    fn kind(&self) -> StrategyKind {
+        StrategyKind::DeathBlossom
+    }
+
+    fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
+        let sets = find_als(board, MAX_PETAL_CELLS);
+        let stems: Vec<Coord> = board
+            .unsolved_cells()
+            .filter(|&(r, c)| (2..=MAX_STEM_CANDIDATES).contains(&board.candidates(r, c).len()))
+            .collect();
+        for stem in stems {
+            for z in board::digits() {
+                let found = blossom(board, &sets, stem, z).unwrap_or_default();
+                if eliminate_candidates(board, found)? {
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// Find one petal per stem candidate, all holding `z`, and return the
+/// eliminations of `z` they allow.
+fn blossom(
+    board: &Board,
+    sets: &[Als],
+    stem: Coord,
+    z: Digit,
+) -> Option<Vec<(usize, usize, Digit)>> {
+    let stem_digits = board.candidates(stem.0, stem.1);
+    if stem_digits.contains(z) {
+        return None;
+    }
+    let stem_peers = board.peer_coords(stem.0, stem.1);
+    let petals: Vec<Vec<&Als>> = stem_digits
+        .iter()
+        .map(|d| {
+            sets.iter()
+                .filter(|als| {
+                    als.digits.contains(d)
+                        && als.digits.contains(z)
+                        && !als.cells.contains(&stem)
+                        && als
+                            .cells_with(board, d)
+                            .iter()
+                            .all(|cell| stem_peers.contains(cell))
+                })
+                .collect()
+        })
+        .collect();
+    if petals.iter().any(|options| options.is_empty()) {
+        return None;
+    }
+    let targets: Vec<Coord> = board
+        .unsolved_cells()
+        .filter(|&(r, c)| (r, c) != stem && board.candidates(r, c).contains(z))
+        .collect();
+    let mut chosen = Vec::new();
+    choose_petals(board, &petals, z, targets, &mut chosen)
+        .map(|cells| cells.into_iter().map(|(r, c)| (r, c, z)).collect())
+}
+
+/// Pick non-overlapping petals while keeping only the cells that see every
+/// petal position of `z`.
+fn choose_petals<'a>(
+    board: &Board,
+    petals: &[Vec<&'a Als>],
+    z: Digit,
+    targets: Vec<Coord>,
+    chosen: &mut Vec<&'a Als>,
+) -> Option<Vec<Coord>> {
+    let Some((options, rest)) = petals.split_first() else {
+        return Some(targets);
+    };
+    for &als in options {
+        let overlaps = chosen
+            .iter()
+            .any(|other| other.cells.iter().any(|cell| als.cells.contains(cell)));
+        if overlaps {
+            continue;
+        }
+        let z_cells = als.cells_with(board, z);
+        let remaining: Vec<Coord> = targets
+            .iter()
+            .copied()
+            .filter(|&(r, c)| {
+                !als.cells.contains(&(r, c)) && {
+                    let peers = board.peer_coords(r, c);
+                    z_cells.iter().all(|cell| peers.contains(cell))
+                }
+            })
+            .collect();
+        if remaining.is_empty() {
+            continue;
+        }
+        chosen.push(als);
+        let found = choose_petals(board, rest, z, remaining, chosen);
+        chosen.pop();
+        if found.is_some() {
+            return found;
+        }
+    }
+    None
+}
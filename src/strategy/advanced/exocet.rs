@@ -0,0 +1,299 @@
+//! Exocet patterns.
+//!
+//! Two base cells in one mini-row of a band hold two to four base digits.
+//! Two target cells sit in the other two boxes of the band, one in each of
+//! the other two rows, and the companion cells sharing their columns in the
+//! band hold no base digit. The cross-lines are the columns of both targets
+//! and the third column of the base box. When every base digit appears at most
+//! twice in the cross-lines outside the band, each digit placed in a base cell
+//! must also be placed in one of the targets, so the targets hold exactly the
+//! digits of the base cells. Stacks are handled by transposing the grid.
+
+use crate::SolverError;
+use crate::board::{Board, CandidateSet, Digit, Unit};
+use crate::strategy::{Strategy, StrategyKind, eliminate_candidates};
+
+const MIN_BASE_DIGITS: usize = 2;
+const MAX_BASE_DIGITS: usize = 4;
+
+type Coord = (usize, usize);
+
+/// Houses allowed to cover the cross-line cells of a base digit.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Cover {
+    /// Two lines parallel to the band, as in the junior exocet.
+    Lines,
+    /// Any two rows, columns or boxes.
+    Houses,
+}
+
+/// Junior Exocet: the cross-line cells of each base digit lie in at most two
+/// lines parallel to the band.
+pub struct JuniorExocet;
+
+impl Strategy for JuniorExocet {
+    fn kind(&self) -> StrategyKind {
+        StrategyKind::JuniorExocet
+    }
+
+    fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
+        apply_exocet(board, Cover::Lines)
+    }
+}
+
+/// Full Exocet: the cross-line cells of each base digit may be covered by any
+/// two houses, including boxes and the cross-lines themselves.
+pub struct Exocet;
+
+impl Strategy for Exocet {
+    fn kind(&self) -> StrategyKind {
+        StrategyKind::Exocet
+    }
+
+    fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
+        apply_exocet(board, Cover::Houses)
+    }
+}
+
+/// Board coordinates seen along bands, or along stacks when transposed.
+#[derive(Clone, Copy)]
+struct Orientation {
+    transposed: bool,
+}
+
+impl Orientation {
+    fn at(self, line: usize, pos: usize) -> Coord {
+        if self.transposed {
+            (pos, line)
+        } else {
+            (line, pos)
+        }
+    }
+}
+
+struct Pattern {
+    base: [Coord; 2],
+    targets: [Coord; 2],
+    digits: CandidateSet,
+}
+
+/// Two empty base cells of a mini-row with their digits, and the third
+/// column of their box, which is a cross-line.
+struct Base {
+    row: usize,
+    stack: usize,
+    cross: usize,
+    cells: [Coord; 2],
+    digits: CandidateSet,
+}
+
+/// Target cells in the other two rows of the band and the other two boxes,
+/// with the companion cells sharing their columns.
+struct Targets {
+    cells: [Coord; 2],
+    companions: [Coord; 2],
+    columns: [usize; 2],
+}
+
+fn apply_exocet(board: &mut Board, cover: Cover) -> Result<bool, SolverError> {
+    for transposed in [false, true] {
+        let orient = Orientation { transposed };
+        for pattern in patterns(board, orient, cover) {
+            if eliminate_candidates(board, eliminations(board, &pattern))? {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+fn others(block: usize, excluded: &[usize]) -> Vec<usize> {
+    (block * 3..block * 3 + 3)
+        .filter(|i| !excluded.contains(i))
+        .collect()
+}
+
+/// The three ways to pick two of the lines of a block, with the third line.
+fn split_block(block: usize) -> [(usize, usize, usize); 3] {
+    let first = block * 3;
+    [
+        (first + 1, first + 2, first),
+        (first, first + 2, first + 1),
+        (first, first + 1, first + 2),
+    ]
+}
+
+fn patterns(board: &Board, orient: Orientation, cover: Cover) -> Vec<Pattern> {
+    let mut found = Vec::new();
+    for base in bases(board, orient) {
+        for targets in target_pairs(&base, orient) {
+            if fits(board, orient, &base, &targets, cover) {
+                found.push(Pattern {
+                    base: base.cells,
+                    targets: targets.cells,
+                    digits: base.digits,
+                });
+            }
+        }
+    }
+    found
+}
+
+/// Pairs of empty cells in a mini-row holding two to four digits between
+/// them.
+fn bases(board: &Board, orient: Orientation) -> Vec<Base> {
+    let mut found = Vec::new();
+    for row in 0..9 {
+        for stack in 0..3 {
+            for (ca, cb, cross) in split_block(stack) {
+                let cells = [orient.at(row, ca), orient.at(row, cb)];
+                if cells.iter().any(|&(r, c)| board.get(r, c).is_some()) {
+                    continue;
+                }
+                let digits = board
+                    .candidates(cells[0].0, cells[0].1)
+                    .union(board.candidates(cells[1].0, cells[1].1));
+                if (MIN_BASE_DIGITS..=MAX_BASE_DIGITS).contains(&digits.len()) {
+                    found.push(Base {
+                        row,
+                        stack,
+                        cross,
+                        cells,
+                        digits,
+                    });
+                }
+            }
+        }
+    }
+    found
+}
+
+/// Every placement of the targets for the base cells.
+fn target_pairs(base: &Base, orient: Orientation) -> Vec<Targets> {
+    let rows = others(base.row / 3, &[base.row]);
+    let stacks: Vec<usize> = (0..3).filter(|&s| s != base.stack).collect();
+    let mut found = Vec::new();
+    for (r1, r2) in [(rows[0], rows[1]), (rows[1], rows[0])] {
+        for c1 in stacks[0] * 3..stacks[0] * 3 + 3 {
+            for c2 in stacks[1] * 3..stacks[1] * 3 + 3 {
+                found.push(Targets {
+                    cells: [orient.at(r1, c1), orient.at(r2, c2)],
+                    companions: [orient.at(r2, c1), orient.at(r1, c2)],
+                    columns: [c1, c2],
+                });
+            }
+        }
+    }
+    found
+}
+
+/// The targets may hold base digits, the companions hold none, and every
+/// base digit is covered in the cross-lines.
+fn fits(board: &Board, orient: Orientation, base: &Base, targets: &Targets, cover: Cover) -> bool {
+    let [c1, c2] = targets.columns;
+    let cross = [base.cross, c1, c2];
+    targets
+        .cells
+        .iter()
+        .all(|&t| is_target(board, t, base.digits))
+        && targets
+            .companions
+            .iter()
+            .all(|&(r, c)| !holds_any(board, r, c, base.digits))
+        && base
+            .digits
+            .iter()
+            .all(|d| covered(board, orient, base.row / 3, cross, d, cover))
+}
+
+fn is_target(board: &Board, (r, c): Coord, digits: CandidateSet) -> bool {
+    board.get(r, c).is_none() && !board.candidates(r, c).intersection(digits).is_empty()
+}
+
+/// Check whether the cell is solved with, or still holds, one of the digits.
+fn holds_any(board: &Board, r: usize, c: usize, digits: CandidateSet) -> bool {
+    match board.get(r, c) {
+        Some(v) => digits.contains(v),
+        None => !board.candidates(r, c).intersection(digits).is_empty(),
+    }
+}
+
+/// The cells of the cross-lines outside the band that may hold the digit
+/// must be covered by two houses, so the digit appears there at most twice.
+fn covered(
+    board: &Board,
+    orient: Orientation,
+    band: usize,
+    cross: [usize; 3],
+    digit: Digit,
+    cover: Cover,
+) -> bool {
+    let mut single = CandidateSet::empty();
+    single.insert(digit);
+    let cells: Vec<Coord> = (0..9)
+        .filter(|line| line / 3 != band)
+        .flat_map(|line| cross.iter().map(move |&pos| orient.at(line, pos)))
+        .filter(|&(r, c)| holds_any(board, r, c, single))
+        .collect();
+    match cover {
+        Cover::Lines => {
+            let mut lines: Vec<usize> = cells
+                .iter()
+                .map(|&(r, c)| if orient.transposed { c } else { r })
+                .collect();
+            lines.sort_unstable();
+            lines.dedup();
+            lines.len() <= 2
+        }
+        Cover::Houses => covered_by_two_houses(&cells),
+    }
+}
+
+fn houses((r, c): Coord) -> [Unit; 3] {
+    [Unit::Row(r), Unit::Col(c), Unit::Box(r / 3 * 3, c / 3 * 3)]
+}
+
+fn covered_by_two_houses(cells: &[Coord]) -> bool {
+    let Some(&first) = cells.first() else {
+        return true;
+    };
+    houses(first).into_iter().any(|house| {
+        let rest: Vec<Coord> = cells
+            .iter()
+            .copied()
+            .filter(|&(r, c)| !house.contains(r, c))
+            .collect();
+        match rest.first() {
+            None => true,
+            Some(&next) => houses(next)
+                .into_iter()
+                .any(|other| rest.iter().all(|&(r, c)| other.contains(r, c))),
+        }
+    })
+}
+
+/// The targets hold only base digits, and a base digit missing from both
+/// targets cannot be placed in the base cells.
+fn eliminations(board: &Board, pattern: &Pattern) -> Vec<(usize, usize, Digit)> {
+    let target_digits = pattern
+        .targets
+        .iter()
+        .fold(CandidateSet::empty(), |acc, &(r, c)| {
+            acc.union(board.candidates(r, c))
+        });
+    let from_targets = pattern.targets.iter().flat_map(|&(r, c)| {
+        board
+            .candidates(r, c)
+            .difference(pattern.digits)
+            .iter()
+            .map(move |d| (r, c, d))
+    });
+    let from_base = pattern.base.iter().flat_map(|&(r, c)| {
+        board
+            .candidates(r, c)
+            .difference(target_digits)
+            .iter()
+            .map(move |d| (r, c, d))
+    });
+    from_targets.chain(from_base).collect()
+}
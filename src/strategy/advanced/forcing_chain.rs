@@ -1,50 +1,289 @@
-use crate::board::{self, Board};
-use crate::strategy::{Strategy, StrategyKind};
+//! Forcing chains.
+//!
+//! Every technique here makes a set of assumptions of which exactly one holds,
+//! follows each of them with an inner solver and keeps what they agree on: a
+//! value placed in every outcome is placed, a candidate removed in every
+//! outcome is removed. An assumption leading to a contradiction is dropped,
+//! and when only one is left everything it implies holds.
+
+use crate::board::{self, Board, Digit, Unit};
+use crate::budget::SolveContext;
+use crate::strategy::{self, Strategy, StrategyKind};
 use crate::{Solver, SolverError};
 
-pub struct ForcingChain;
+/// Largest number of candidates of a cell used as a chain source.
+const MAX_CELL_CANDIDATES: usize = 4;
+/// Largest number of positions of a digit in a unit used as a chain source.
+const MAX_UNIT_POSITIONS: usize = 4;
+
+/// Strategies the inner solver follows an assumption with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChainDepth {
+    /// Naked and hidden singles, as in classic forcing chains.
+    Singles,
+    /// Singles, subsets and locked candidates.
+    #[default]
+    Basic,
+    /// Every strategy that does not make assumptions itself.
+    Full,
+}
+
+impl ChainDepth {
+    pub(crate) fn solver(self) -> Solver {
+        let kinds: &[StrategyKind] = match self {
+            ChainDepth::Singles => &[StrategyKind::SingleCandidate, StrategyKind::HiddenSingle],
+            ChainDepth::Basic => &[
+                StrategyKind::SingleCandidate,
+                StrategyKind::HiddenSingle,
+                StrategyKind::NakedPair,
+                StrategyKind::NakedTriple,
+                StrategyKind::NakedQuad,
+                StrategyKind::HiddenPair,
+                StrategyKind::HiddenTriple,
+                StrategyKind::HiddenQuad,
+                StrategyKind::LockedCandidatesType1,
+                StrategyKind::LockedCandidatesType2,
+            ],
+            ChainDepth::Full => return Solver::without_nishio_and_forcing_chain(),
+        };
+        Solver::new(
+            kinds
+                .iter()
+                .map(|&k| strategy::kind_to_strategy(k))
+                .collect(),
+        )
+    }
+}
+
+/// Cell Forcing Chain: each candidate of a cell is assumed in turn.
+pub struct CellForcingChain {
+    depth: ChainDepth,
+}
+
+/// Unit Forcing Chain: each position of a digit in a unit is assumed in turn.
+pub struct UnitForcingChain {
+    depth: ChainDepth,
+}
+
+/// Dynamic Forcing Chain: a candidate is assumed both placed and removed.
+///
+/// The cell and unit chains assume each alternative of one cell or unit, so
+/// they only take sources with a few alternatives. Placing and removing a
+/// candidate covers every case on its own, so any candidate of any cell is a
+/// source here. A contradiction on one side settles the candidate, and
+/// whatever both sides agree on holds.
+pub struct DynamicForcingChain {
+    depth: ChainDepth,
+}
+
+impl CellForcingChain {
+    pub const fn new(depth: ChainDepth) -> Self {
+        Self { depth }
+    }
+}
+
+impl UnitForcingChain {
+    pub const fn new(depth: ChainDepth) -> Self {
+        Self { depth }
+    }
+}
+
+impl DynamicForcingChain {
+    pub const fn new(depth: ChainDepth) -> Self {
+        Self { depth }
+    }
+}
+
+impl Default for CellForcingChain {
+    fn default() -> Self {
+        Self::new(ChainDepth::default())
+    }
+}
+
+impl Default for UnitForcingChain {
+    fn default() -> Self {
+        Self::new(ChainDepth::default())
+    }
+}
 
-impl Strategy for ForcingChain {
+impl Default for DynamicForcingChain {
+    fn default() -> Self {
+        Self::new(ChainDepth::default())
+    }
+}
+
+impl Strategy for CellForcingChain {
     fn kind(&self) -> StrategyKind {
-        StrategyKind::ForcingChain
+        StrategyKind::CellForcingChain
     }
 
     fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
-        let mut target = None;
-        let mut best_len = 10;
-        for (r, c) in board.unsolved_cells() {
-            let cands = board.candidates(r, c);
-            let len = cands.len();
-            if len > 1 && len < best_len {
-                best_len = len;
-                target = Some((r, c, cands));
-                if len == 2 {
-                    break;
-                }
+        self.apply_in(board, &SolveContext::default())
+    }
+
+    fn apply_in(&self, board: &mut Board, context: &SolveContext) -> Result<bool, SolverError> {
+        let solver = self.depth.solver();
+        let cells: Vec<_> = board
+            .unsolved_cells()
+            .filter(|&(r, c)| (2..=MAX_CELL_CANDIDATES).contains(&board.candidates(r, c).len()))
+            .collect();
+        for (r, c) in cells {
+            let assumptions = board
+                .candidates(r, c)
+                .iter()
+                .map(|d| Assumption::Place(r, c, d));
+            if apply_common(board, &solver, context, (r, c), assumptions)? {
+                return Ok(true);
             }
         }
+        Ok(false)
+    }
+}
 
-        let (r, c, cands) = match target {
-            Some(t) => t,
-            None => return Ok(false),
-        };
+impl Strategy for UnitForcingChain {
+    fn kind(&self) -> StrategyKind {
+        StrategyKind::UnitForcingChain
+    }
+
+    fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
+        self.apply_in(board, &SolveContext::default())
+    }
 
-        let mut solution = None;
-        for d in cands.iter() {
-            let mut trial = board.clone();
-            trial.set(r, c, d);
-            let solver = Solver::without_nishio_and_forcing_chain();
-            if solver.solve(&mut trial).is_ok() {
-                if solution.is_some() {
-                    return Ok(false);
+    fn apply_in(&self, board: &mut Board, context: &SolveContext) -> Result<bool, SolverError> {
+        let solver = self.depth.solver();
+        for unit in Unit::all() {
+            for d in board::digits() {
+                let positions = board.candidate_coords(unit, d);
+                let placed = board
+                    .unit_iter(unit)
+                    .any(|(r, c)| board.get(r, c) == Some(d));
+                if placed || !(2..=MAX_UNIT_POSITIONS).contains(&positions.len()) {
+                    continue;
+                }
+                let source = positions.into_iter().next().unwrap_or_default();
+                let assumptions: Vec<_> = positions
+                    .into_iter()
+                    .map(|(r, c)| Assumption::Place(r, c, d))
+                    .collect();
+                if apply_common(board, &solver, context, source, assumptions)? {
+                    return Ok(true);
                 }
-                solution = Some(d);
             }
         }
-        if let Some(d) = solution {
-            board.set(r, c, d);
-            return Ok(true);
+        Ok(false)
+    }
+}
+
+impl Strategy for DynamicForcingChain {
+    fn kind(&self) -> StrategyKind {
+        StrategyKind::DynamicForcingChain
+    }
+
+    fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
+        self.apply_in(board, &SolveContext::default())
+    }
+
+    fn apply_in(&self, board: &mut Board, context: &SolveContext) -> Result<bool, SolverError> {
+        let solver = self.depth.solver();
+        let cells: Vec<_> = board.unsolved_cells().collect();
+        for (r, c) in cells {
+            for d in board.candidates(r, c).iter() {
+                let assumptions = [Assumption::Place(r, c, d), Assumption::Remove(r, c, d)];
+                if apply_common(board, &solver, context, (r, c), assumptions)? {
+                    return Ok(true);
+                }
+            }
         }
         Ok(false)
     }
 }
+
+#[derive(Clone, Copy)]
+pub(crate) enum Assumption {
+    Place(usize, usize, Digit),
+    Remove(usize, usize, Digit),
+}
+
+/// Follow the assumption, returning `None` when it leads to a contradiction.
+/// Fails only when the solve is cancelled or runs out of its budget.
+pub(crate) fn follow(
+    board: &Board,
+    solver: &Solver,
+    context: &SolveContext,
+    assumption: Assumption,
+) -> Result<Option<Board>, SolverError> {
+    let mut trial = board.clone();
+    match assumption {
+        Assumption::Place(r, c, d) => trial.set(r, c, d),
+        Assumption::Remove(r, c, d) => {
+            if trial.eliminate_candidate(r, c, d).is_none() {
+                return Ok(None);
+            }
+        }
+    }
+    match solver.reduce_in(&mut trial, context) {
+        Ok(_) => Ok(is_consistent(&trial).then_some(trial)),
+        Err(e @ (SolverError::BudgetExceeded { .. } | SolverError::Cancelled { .. })) => Err(e),
+        Err(_) => Ok(None),
+    }
+}
+
+/// No duplicate values, no cell without candidates and no digit without a
+/// position in a unit.
+fn is_consistent(board: &Board) -> bool {
+    board.is_valid()
+        && board
+            .unsolved_cells()
+            .all(|(r, c)| !board.candidates(r, c).is_empty())
+        && Unit::all().all(|unit| {
+            board::digits().all(|d| board.unit_iter(unit).any(|(r, c)| holds(board, r, c, d)))
+        })
+}
+
+/// Apply the deductions shared by every outcome of the assumptions.
+fn apply_common<I>(
+    board: &mut Board,
+    solver: &Solver,
+    context: &SolveContext,
+    source: (usize, usize),
+    assumptions: I,
+) -> Result<bool, SolverError>
+where
+    I: IntoIterator<Item = Assumption>,
+{
+    let mut outcomes = Vec::new();
+    for assumption in assumptions {
+        outcomes.extend(follow(board, solver, context, assumption)?);
+    }
+    if outcomes.is_empty() {
+        return Err(SolverError::contradiction(source.0, source.1, None));
+    }
+    let mut placements = Vec::new();
+    let mut eliminations = Vec::new();
+    for (r, c) in board.unsolved_cells() {
+        let value = outcomes[0].get(r, c);
+        if value.is_some() && outcomes.iter().all(|o| o.get(r, c) == value) {
+            placements.extend(value.map(|v| (r, c, v)));
+            continue;
+        }
+        eliminations.extend(
+            board
+                .candidates(r, c)
+                .iter()
+                .filter(|&d| outcomes.iter().all(|o| !holds(o, r, c, d)))
+                .map(|d| (r, c, d)),
+        );
+    }
+    let placed = !placements.is_empty();
+    for (r, c, d) in placements {
+        board.set(r, c, d);
+    }
+    Ok(strategy::eliminate_candidates(board, eliminations)? || placed)
+}
+
+pub(crate) fn holds(board: &Board, r: usize, c: usize, digit: Digit) -> bool {
+    match board.get(r, c) {
+        Some(v) => v == digit,
+        None => board.candidates(r, c).contains(digit),
+    }
+}
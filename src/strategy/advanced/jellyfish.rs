@@ -1,3 +1,4 @@
+use super::swordfish::eliminate_from_cover;
 use crate::SolverError;
 use crate::board::{self, Board};
 use crate::strategy::{Strategy, StrategyKind};
@@ -28,25 +29,15 @@ impl Strategy for Jellyfish {
                             union.extend(cols2.iter());
                             union.extend(cols3.iter());
                             union.extend(cols4.iter());
-                            if cols1.len() <= FISH_LEN
-                                && cols2.len() <= FISH_LEN
-                                && cols3.len() <= FISH_LEN
-                                && cols4.len() <= FISH_LEN
+                            if (1..=FISH_LEN).contains(&cols1.len())
+                                && (1..=FISH_LEN).contains(&cols2.len())
+                                && (1..=FISH_LEN).contains(&cols3.len())
+                                && (1..=FISH_LEN).contains(&cols4.len())
                                 && union.len() == FISH_LEN
                             {
-                                let changed = board::row_indices()
-                                    .filter(|&r| r != r1 && r != r2 && r != r3 && r != r4)
-                                    .flat_map(|r| union.iter().map(move |&c| (r, c)))
-                                    .try_fold(false, |acc, (r, c)| {
-                                        match board.eliminate_candidate(r, c, digit) {
-                                            Some(true) => Ok(true),
-                                            Some(false) => Ok(acc),
-                                            None => {
-                                                Err(SolverError::Contradiction { row: r, col: c })
-                                            }
-                                        }
-                                    })?;
-                                if changed {
+                                let base = [r1, r2, r3, r4];
+                                let cover: Vec<_> = union.into_iter().collect();
+                                if eliminate_from_cover(board, digit, &base, &cover, false)? {
                                     return Ok(true);
                                 }
                             }
@@ -72,25 +63,15 @@ impl Strategy for Jellyfish {
                             union.extend(rows2.iter());
                             union.extend(rows3.iter());
                             union.extend(rows4.iter());
-                            if rows1.len() <= FISH_LEN
-                                && rows2.len() <= FISH_LEN
-                                && rows3.len() <= FISH_LEN
-                                && rows4.len() <= FISH_LEN
+                            if (1..=FISH_LEN).contains(&rows1.len())
+                                && (1..=FISH_LEN).contains(&rows2.len())
+                                && (1..=FISH_LEN).contains(&rows3.len())
+                                && (1..=FISH_LEN).contains(&rows4.len())
                                 && union.len() == FISH_LEN
                             {
-                                let changed = board::col_indices()
-                                    .filter(|&c| c != c1 && c != c2 && c != c3 && c != c4)
-                                    .flat_map(|c| union.iter().map(move |&r| (r, c)))
-                                    .try_fold(false, |acc, (r, c)| {
-                                        match board.eliminate_candidate(r, c, digit) {
-                                            Some(true) => Ok(true),
-                                            Some(false) => Ok(acc),
-                                            None => {
-                                                Err(SolverError::Contradiction { row: r, col: c })
-                                            }
-                                        }
-                                    })?;
-                                if changed {
+                                let base = [c1, c2, c3, c4];
+                                let cover: Vec<_> = union.into_iter().collect();
+                                if eliminate_from_cover(board, digit, &base, &cover, true)? {
                                     return Ok(true);
                                 }
                             }
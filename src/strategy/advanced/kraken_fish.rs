@@ -0,0 +1,150 @@
+use super::forcing_chain::{Assumption, ChainDepth, follow, holds};
+use crate::SolverError;
+use crate::board::{self, Board, Digit};
+use crate::budget::SolveContext;
+use crate::strategy::{Strategy, StrategyKind, eliminate_candidates, subsets};
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::ops::RangeInclusive;
+
+/// Fish sizes searched: X-Wing, Swordfish and Jellyfish.
+pub const FISH_SIZES: RangeInclusive<usize> = 2..=4;
+/// Largest number of fins followed for one fish.
+const MAX_FINS: usize = 4;
+
+type Coord = (usize, usize);
+
+/// Kraken Fish.
+///
+/// A finned fish removes its digit from a cover line cell outside the base
+/// lines unless one of the fins holds the digit. When following every fin
+/// with a forcing chain also removes the digit from that cell, the cell
+/// loses it either way.
+pub struct KrakenFish {
+    depth: ChainDepth,
+    max_size: usize,
+}
+
+impl KrakenFish {
+    pub const fn new(depth: ChainDepth) -> Self {
+        Self {
+            depth,
+            max_size: *FISH_SIZES.end(),
+        }
+    }
+
+    /// Only search fish with at most `max_size` base lines.
+    pub const fn with_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+}
+
+impl Default for KrakenFish {
+    fn default() -> Self {
+        Self::new(ChainDepth::default())
+    }
+}
+
+impl Strategy for KrakenFish {
+    fn kind(&self) -> StrategyKind {
+        StrategyKind::KrakenFish
+    }
+
+    fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
+        self.apply_in(board, &SolveContext::default())
+    }
+
+    fn apply_in(&self, board: &mut Board, context: &SolveContext) -> Result<bool, SolverError> {
+        let solver = self.depth.solver();
+        for digit in board::digits() {
+            // each fin is followed once per digit, `None` marks a fin that
+            // cannot hold the digit
+            let mut outcomes: HashMap<Coord, Option<Board>> = HashMap::new();
+            for transposed in [false, true] {
+                for fish in finned_fish(board, digit, transposed, self.max_size) {
+                    for &(r, c) in &fish.fins {
+                        if let Entry::Vacant(entry) = outcomes.entry((r, c)) {
+                            let assumption = Assumption::Place(r, c, digit);
+                            entry.insert(follow(board, &solver, context, assumption)?);
+                        }
+                    }
+                    let found: Vec<_> = fish
+                        .targets
+                        .iter()
+                        .filter(|&&(r, c)| {
+                            fish.fins.iter().all(|fin| match &outcomes[fin] {
+                                Some(outcome) => !holds(outcome, r, c, digit),
+                                None => true,
+                            })
+                        })
+                        .map(|&(r, c)| (r, c, digit))
+                        .collect();
+                    if eliminate_candidates(board, found)? {
+                        return Ok(true);
+                    }
+                }
+            }
+        }
+        Ok(false)
+    }
+}
+
+struct Fish {
+    fins: Vec<Coord>,
+    targets: Vec<Coord>,
+}
+
+/// Finned fish of the digit with up to `max_size` rows as base lines, or
+/// columns when transposed.
+fn finned_fish(board: &Board, digit: Digit, transposed: bool, max_size: usize) -> Vec<Fish> {
+    let at = |line: usize, pos: usize| if transposed { (pos, line) } else { (line, pos) };
+    let positions: Vec<Vec<usize>> = (0..9)
+        .map(|line| {
+            (0..9)
+                .filter(|&pos| {
+                    let (r, c) = at(line, pos);
+                    board.get(r, c).is_none() && board.candidates(r, c).contains(digit)
+                })
+                .collect()
+        })
+        .collect();
+    let lines: Vec<usize> = (0..9).filter(|&l| !positions[l].is_empty()).collect();
+
+    let mut found = Vec::new();
+    for size in FISH_SIZES.filter(|&size| size <= max_size) {
+        for base in subsets(&lines, size, size) {
+            let mut used: Vec<usize> = base.iter().flat_map(|&l| positions[l].clone()).collect();
+            used.sort_unstable();
+            used.dedup();
+            for cover in subsets(&used, size, size) {
+                let fins: Vec<Coord> = base
+                    .iter()
+                    .flat_map(|&l| {
+                        positions[l]
+                            .iter()
+                            .filter(|pos| !cover.contains(pos))
+                            .map(move |&pos| at(l, pos))
+                    })
+                    .collect();
+                if fins.is_empty() || fins.len() > MAX_FINS {
+                    continue;
+                }
+                let positions = &positions;
+                let targets: Vec<Coord> = (0..9)
+                    .filter(|line| !base.contains(line))
+                    .flat_map(|line| {
+                        cover
+                            .iter()
+                            .filter(move |&&pos| positions[line].contains(&pos))
+                            .map(move |&pos| at(line, pos))
+                    })
+                    .collect();
+                if !targets.is_empty() {
+                    found.push(Fish { fins, targets });
+                }
+            }
+        }
+    }
+    found
+}
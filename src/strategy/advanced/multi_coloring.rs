@@ -0,0 +1,96 @@
+use super::coloring::{color_components, conjugate_pairs};
+use crate::SolverError;
+use crate::board::{self, Board, Digit};
+use crate::strategy::{Strategy, StrategyKind, eliminate_candidates};
+use std::collections::HashSet;
+
+type Cells = HashSet<(usize, usize)>;
+
+/// Multi-coloring relates two conjugate clusters of the same digit. When a
+/// color of one cluster sees a color of the other, at least one of the two
+/// opposite colors is true.
+pub struct MultiColoring;
+
+impl Strategy for MultiColoring {
+    fn kind(&self) -> StrategyKind {
+        StrategyKind::MultiColoring
+    }
+
+    fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
+        for digit in board::digits() {
+            let clusters: Vec<[Cells; 2]> = color_components(&conjugate_pairs(board, digit))
+                .iter()
+                .map(|component| component.color_sets())
+                .collect();
+            for (i, first) in clusters.iter().enumerate() {
+                for (j, second) in clusters.iter().enumerate() {
+                    if i == j {
+                        continue;
+                    }
+                    let eliminations: Vec<_> = color_wrap(board, first, second)
+                        .into_iter()
+                        .chain(color_trap(board, digit, first, second))
+                        .collect();
+                    if eliminate_candidates(
+                        board,
+                        eliminations.into_iter().map(|(r, c)| (r, c, digit)),
+                    )? {
+                        return Ok(true);
+                    }
+                }
+            }
+        }
+        Ok(false)
+    }
+}
+
+fn sees_any(board: &Board, (r, c): (usize, usize), cells: &Cells) -> bool {
+    board
+        .peer_coords(r, c)
+        .iter()
+        .any(|peer| cells.contains(peer))
+}
+
+fn sets_see(board: &Board, a: &Cells, b: &Cells) -> bool {
+    a.iter().any(|&cell| sees_any(board, cell, b))
+}
+
+/// A color of the first cluster that sees both colors of the second cluster
+/// can never be true.
+fn color_wrap(board: &Board, first: &[Cells; 2], second: &[Cells; 2]) -> Vec<(usize, usize)> {
+    first
+        .iter()
+        .filter(|color| second.iter().all(|other| sets_see(board, color, other)))
+        .flat_map(|color| color.iter().copied())
+        .collect()
+}
+
+/// When color `a` of the first cluster sees color `b` of the second, one of
+/// the opposite colors must be true, so cells seeing both opposites lose the
+/// digit.
+fn color_trap(
+    board: &Board,
+    digit: Digit,
+    first: &[Cells; 2],
+    second: &[Cells; 2],
+) -> Vec<(usize, usize)> {
+    let mut eliminations = Vec::new();
+    for a in 0..2 {
+        for b in 0..2 {
+            if !sets_see(board, &first[a], &second[b]) {
+                continue;
+            }
+            let (opp_a, opp_b) = (&first[1 - a], &second[1 - b]);
+            for (r, c) in board.unsolved_cells() {
+                if board.candidates(r, c).contains(digit)
+                    && !first.iter().chain(second).any(|set| set.contains(&(r, c)))
+                    && sees_any(board, (r, c), opp_a)
+                    && sees_any(board, (r, c), opp_b)
+                {
+                    eliminations.push((r, c));
+                }
+            }
+        }
+    }
+    eliminations
+}
@@ -0,0 +1,249 @@
+//! Multi-sector locked sets.
+//!
+//! A group of cells is covered by several sectors, each of which can hold a
+//! digit at most once. When the number of placements all sectors can still
+//! offer equals the number of cells, every sector is used to the full and
+//! its digits leave the sector outside the group.
+
+use crate::SolverError;
+use crate::board::{self, Board, CandidateSet, Digit, Unit};
+use crate::strategy::{Strategy, StrategyKind, eliminate_candidates, subsets};
+
+/// Sizes of the row and column sets searched for a multi-sector locked set.
+const MIN_LINES: usize = 2;
+const MAX_LINES: usize = 4;
+
+type Coord = (usize, usize);
+
+/// Multi-Sector Locked Set over a set of rows and a set of columns.
+///
+/// In the unsolved cells where the rows meet the columns, each digit appears
+/// at most once per row holding it there, and once per column. Taking the
+/// smaller of both counts for every digit, if the counts add up to the number
+/// of cells, each digit fills all of the rows (or columns) giving its count.
+pub struct MultiSectorLockedSet;
+
+impl Strategy for MultiSectorLockedSet {
+    fn kind(&self) -> StrategyKind {
+        StrategyKind::MultiSectorLockedSet
+    }
+
+    fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
+        let rows = line_sets(board, |line, pos| (line, pos));
+        let cols = line_sets(board, |line, pos| (pos, line));
+        for row_set in &rows {
+            for col_set in &cols {
+                let found = locked_set(board, row_set, col_set);
+                if eliminate_candidates(board, found)? {
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// Subsets of rows (or columns) with at least one unsolved cell each.
+fn line_sets(board: &Board, at: impl Fn(usize, usize) -> Coord) -> Vec<Vec<usize>> {
+    let open: Vec<usize> = (0..9)
+        .filter(|&line| (0..9).any(|pos| board.get(at(line, pos).0, at(line, pos).1).is_none()))
+        .collect();
+    subsets(&open, MIN_LINES, MAX_LINES)
+}
+
+fn locked_set(board: &Board, rows: &[usize], cols: &[usize]) -> Vec<(usize, usize, Digit)> {
+    let cells: Vec<Coord> = rows
+        .iter()
+        .flat_map(|&r| cols.iter().map(move |&c| (r, c)))
+        .filter(|&(r, c)| board.get(r, c).is_none())
+        .collect();
+    if cells.len() < MIN_LINES * MIN_LINES {
+        return Vec::new();
+    }
+    let mut capacity = 0;
+    // for each digit the rows and columns it has to fill
+    let mut filled: Vec<(Digit, Vec<usize>, Vec<usize>)> = Vec::new();
+    for d in board::digits() {
+        let holding: Vec<Coord> = cells
+            .iter()
+            .copied()
+            .filter(|&(r, c)| board.candidates(r, c).contains(d))
+            .collect();
+        let mut in_rows: Vec<usize> = holding.iter().map(|&(r, _)| r).collect();
+        in_rows.sort_unstable();
+        in_rows.dedup();
+        let mut in_cols: Vec<usize> = holding.iter().map(|&(_, c)| c).collect();
+        in_cols.sort_unstable();
+        in_cols.dedup();
+        let count = in_rows.len().min(in_cols.len());
+        capacity += count;
+        if capacity > cells.len() {
+            return Vec::new();
+        }
+        let by_rows = if in_rows.len() == count {
+            in_rows
+        } else {
+            Vec::new()
+        };
+        let by_cols = if in_cols.len() == count {
+            in_cols
+        } else {
+            Vec::new()
+        };
+        filled.push((d, by_rows, by_cols));
+    }
+    if capacity != cells.len() {
+        return Vec::new();
+    }
+    let mut found = Vec::new();
+    for (d, by_rows, by_cols) in filled {
+        for &r in &by_rows {
+            found.extend(
+                (0..9)
+                    .filter(|c| !cols.contains(c))
+                    .filter(|&c| board.get(r, c).is_none() && board.candidates(r, c).contains(d))
+                    .map(|c| (r, c, d)),
+            );
+        }
+        for &c in &by_cols {
+            found.extend(
+                (0..9)
+                    .filter(|r| !rows.contains(r))
+                    .filter(|&r| board.get(r, c).is_none() && board.candidates(r, c).contains(d))
+                    .map(|r| (r, c, d)),
+            );
+        }
+    }
+    found
+}
+
+/// SK Loop.
+///
+/// Two rows from different bands and two columns from different stacks meet
+/// in four boxes. In each box the other two cells of the row and the other two
+/// cells of the column form two segments, and the eight segments are joined
+/// into a loop alternately by the lines and by the boxes. Each link places
+/// its digits at most once in its two segments, so when every candidate of a
+/// segment belongs to one of its links and the links hold sixteen digits, all
+/// of them are placed in the loop and leave the rest of the linking unit.
+pub struct SkLoop;
+
+impl Strategy for SkLoop {
+    fn kind(&self) -> StrategyKind {
+        StrategyKind::SkLoop
+    }
+
+    fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
+        for (r1, r2) in board::row_pairs().filter(|&(a, b)| a / 3 != b / 3) {
+            for (c1, c2) in board::col_pairs().filter(|&(a, b)| a / 3 != b / 3) {
+                let found = sk_loop(board, [r1, r2], [c1, c2]);
+                if eliminate_candidates(board, found)? {
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+}
+
+fn segment_of_row(r: usize, c: usize) -> [Coord; 2] {
+    let others: Vec<usize> = (c / 3 * 3..c / 3 * 3 + 3).filter(|&x| x != c).collect();
+    [(r, others[0]), (r, others[1])]
+}
+
+fn segment_of_col(r: usize, c: usize) -> [Coord; 2] {
+    let others: Vec<usize> = (r / 3 * 3..r / 3 * 3 + 3).filter(|&x| x != r).collect();
+    [(others[0], c), (others[1], c)]
+}
+
+fn sk_loop(
+    board: &Board,
+    [r1, r2]: [usize; 2],
+    [c1, c2]: [usize; 2],
+) -> Vec<(usize, usize, Digit)> {
+    let box_of = |r: usize, c: usize| Unit::Box(r / 3 * 3, c / 3 * 3);
+    // segments in loop order, each followed by the unit linking it to the next
+    let loop_order = [
+        (segment_of_row(r1, c1), Unit::Row(r1)),
+        (segment_of_row(r1, c2), box_of(r1, c2)),
+        (segment_of_col(r1, c2), Unit::Col(c2)),
+        (segment_of_col(r2, c2), box_of(r2, c2)),
+        (segment_of_row(r2, c2), Unit::Row(r2)),
+        (segment_of_row(r2, c1), box_of(r2, c1)),
+        (segment_of_col(r2, c1), Unit::Col(c1)),
+        (segment_of_col(r1, c1), box_of(r1, c1)),
+    ];
+    let loop_cells: Vec<Coord> = loop_order.iter().flat_map(|(seg, _)| *seg).collect();
+    if loop_cells.iter().any(|&(r, c)| board.get(r, c).is_some()) {
+        return Vec::new();
+    }
+    let segments: Vec<CandidateSet> = loop_order
+        .iter()
+        .map(|(seg, _)| {
+            seg.iter().fold(CandidateSet::empty(), |acc, &(r, c)| {
+                acc.union(board.candidates(r, c))
+            })
+        })
+        .collect();
+    let shared: Vec<CandidateSet> = (0..segments.len())
+        .map(|i| segments[i].intersection(segments[(i + 1) % segments.len()]))
+        .collect();
+    let mut chosen = Vec::new();
+    if !choose_links(&segments, &shared, loop_cells.len(), &mut chosen) {
+        return Vec::new();
+    }
+    let mut found = Vec::new();
+    for (i, (seg, link)) in loop_order.iter().enumerate() {
+        let (next, _) = &loop_order[(i + 1) % loop_order.len()];
+        let cells = [seg[0], seg[1], next[0], next[1]];
+        for (r, c) in board.unit_iter(*link).filter(|cell| !cells.contains(cell)) {
+            found.extend(
+                board
+                    .candidates(r, c)
+                    .intersection(chosen[i])
+                    .iter()
+                    .map(|d| (r, c, d)),
+            );
+        }
+    }
+    found
+}
+
+/// Pick the digits of each link among those its segments share, so that
+/// every candidate of a segment belongs to one of its two links and the
+/// links add up to exactly `capacity` digits.
+fn choose_links(
+    segments: &[CandidateSet],
+    shared: &[CandidateSet],
+    capacity: usize,
+    chosen: &mut Vec<CandidateSet>,
+) -> bool {
+    let i = chosen.len();
+    let total: usize = chosen.iter().map(|set| set.len()).sum();
+    if i == shared.len() {
+        return total == capacity
+            && segments[0]
+                .difference(chosen[0].union(chosen[i - 1]))
+                .is_empty();
+    }
+    let digits: Vec<Digit> = shared[i].iter().collect();
+    for mask in 0..1u32 << digits.len() {
+        let mut link = CandidateSet::empty();
+        for (bit, &d) in digits.iter().enumerate() {
+            if mask & (1 << bit) != 0 {
+                link.insert(d);
+            }
+        }
+        let covers_previous =
+            i == 0 || segments[i].difference(chosen[i - 1].union(link)).is_empty();
+        if total + link.len() > capacity || !covers_previous {
+            continue;
+        }
+        chosen.push(link);
+        if choose_links(segments, shared, capacity, chosen) {
+            return true;
+        }
+        chosen.pop();
+    }
+    false
+}
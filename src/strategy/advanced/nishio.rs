@@ -1,7 +1,14 @@
-use crate::board::{self, Board};
-use crate::strategy::{Strategy, StrategyKind};
-use crate::{Solver, SolverError};
+use crate::SolverError;
+use crate::board::{self, Board, Digit, Unit};
+use crate::budget::SolveContext;
+use crate::strategy::{Strategy, StrategyKind, eliminate_candidates};
 
+/// Nishio: a single-digit trial.
+///
+/// A candidate is assumed to be placed and only that digit is followed: the
+/// digit leaves the peers of every placement and is placed where a unit keeps
+/// a single position for it. When a unit is left without any position for
+/// the digit, the assumed candidate is removed.
 pub struct Nishio;
 
 impl Strategy for Nishio {
@@ -10,25 +17,77 @@ impl Strategy for Nishio {
     }
 
     fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
-        let cells: Vec<_> = board.unsolved_cells().collect();
-        for (r, c) in cells {
-            let cands = board.candidates(r, c);
-            if cands.len() <= 1 || cands.len() > 4 {
-                continue;
+        self.apply_in(board, &SolveContext::default())
+    }
+
+    fn apply_in(&self, board: &mut Board, context: &SolveContext) -> Result<bool, SolverError> {
+        for d in board::digits() {
+            context.check_interrupted()?;
+            let found: Vec<_> = board
+                .unsolved_cells()
+                .filter(|&(r, c)| board.candidates(r, c).contains(d))
+                .filter(|&cell| DigitTrial::new(board, d).fails_with(board, cell))
+                .map(|(r, c)| (r, c, d))
+                .collect();
+            if eliminate_candidates(board, found)? {
+                return Ok(true);
             }
-            for d in cands.iter() {
-                let mut trial = board.clone();
-                trial.set(r, c, d);
-                let solver = Solver::without_nishio_and_forcing_chain();
-                if solver.solve(&mut trial).is_err() {
-                    match board.eliminate_candidate(r, c, d) {
-                        Some(true) => return Ok(true),
-                        Some(false) => {}
-                        None => return Err(SolverError::Contradiction { row: r, col: c }),
+        }
+        Ok(false)
+    }
+}
+
+/// Positions of one digit, with the cells it is placed in.
+struct DigitTrial {
+    placed: [[bool; 9]; 9],
+    open: [[bool; 9]; 9],
+}
+
+impl DigitTrial {
+    fn new(board: &Board, digit: Digit) -> Self {
+        let mut trial = Self {
+            placed: [[false; 9]; 9],
+            open: [[false; 9]; 9],
+        };
+        for (r, c) in board.cells() {
+            trial.placed[r][c] = board.get(r, c) == Some(digit);
+            trial.open[r][c] = board.get(r, c).is_none() && board.candidates(r, c).contains(digit);
+        }
+        trial
+    }
+
+    /// Place the digit in the cell and follow it until a unit runs out of
+    /// positions or nothing changes.
+    fn fails_with(mut self, board: &Board, cell: (usize, usize)) -> bool {
+        self.place(board, cell);
+        loop {
+            let mut forced = None;
+            for unit in Unit::all() {
+                if board.unit_iter(unit).any(|(r, c)| self.placed[r][c]) {
+                    continue;
+                }
+                let mut open = board.unit_iter(unit).filter(|&(r, c)| self.open[r][c]);
+                match (open.next(), open.next()) {
+                    (None, _) => return true,
+                    (Some(single), None) => {
+                        forced = Some(single);
+                        break;
                     }
+                    _ => {}
                 }
             }
+            match forced {
+                Some(single) => self.place(board, single),
+                None => return false,
+            }
+        }
+    }
+
+    fn place(&mut self, board: &Board, (r, c): (usize, usize)) {
+        self.placed[r][c] = true;
+        self.open[r][c] = false;
+        for (pr, pc) in board.peer_coords(r, c) {
+            self.open[pr][pc] = false;
         }
-        Ok(false)
     }
 }
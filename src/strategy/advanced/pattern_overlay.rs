@@ -0,0 +1,165 @@
+use crate::SolverError;
+use crate::board::{self, Board, Digit};
+use crate::strategy::{Strategy, StrategyKind, eliminate_candidates};
+
+/// Largest number of template pairs compared when combining two digits.
+const MAX_TEMPLATE_PAIRS: usize = 4_000_000;
+
+/// One placement of a digit in every row, column and box, as a bit per cell.
+type Template = u128;
+
+/// Pattern Overlay Method.
+///
+/// Every digit has to end up in one of the 46656 templates: a set of nine
+/// cells covering each row, column and box once. Only templates using cells
+/// where the digit is placed or still a candidate are kept, and a candidate
+/// lying in none of them is removed. With pair combination, a template is
+/// also dropped when no template of another digit avoids all of its cells.
+pub struct PatternOverlay {
+    combine_pairs: bool,
+}
+
+impl PatternOverlay {
+    /// Single-digit templates only.
+    pub const fn new() -> Self {
+        Self {
+            combine_pairs: false,
+        }
+    }
+
+    /// Single-digit templates, then combinations of two digits.
+    pub const fn with_pair_combination() -> Self {
+        Self {
+            combine_pairs: true,
+        }
+    }
+}
+
+impl Default for PatternOverlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Strategy for PatternOverlay {
+    fn kind(&self) -> StrategyKind {
+        StrategyKind::PatternOverlay
+    }
+
+    fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
+        let mut templates = Vec::new();
+        for d in board::digits() {
+            let found = templates_for(board, d);
+            if found.is_empty() {
+                // the digit can no longer be placed in every row, whatever
+                // positions it takes
+                let (r, c) = stuck_cell(board, d);
+                let positions = cells_with(board, d, !0).collect();
+                return Err(SolverError::contradiction(r, c, Some(d)).in_unit(None, positions));
+            }
+            if eliminate_outside(board, d, &found)? {
+                return Ok(true);
+            }
+            templates.push(found);
+        }
+        if !self.combine_pairs {
+            return Ok(false);
+        }
+        for (i, d) in board::digits().enumerate() {
+            for j in (0..templates.len()).filter(|&j| j != i) {
+                let (own, other) = (&templates[i], &templates[j]);
+                if own.len().saturating_mul(other.len()) > MAX_TEMPLATE_PAIRS {
+                    continue;
+                }
+                let compatible: Vec<Template> = own
+                    .iter()
+                    .copied()
+                    .filter(|&t| other.iter().any(|&o| t & o == 0))
+                    .collect();
+                if eliminate_outside(board, d, &compatible)? {
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+}
+
+fn bit(r: usize, c: usize) -> Template {
+    1 << (r * 9 + c)
+}
+
+/// Unsolved cells holding the digit as a candidate, restricted to the mask.
+fn cells_with(
+    board: &Board,
+    digit: Digit,
+    mask: Template,
+) -> impl Iterator<Item = (usize, usize)> + '_ {
+    board
+        .unsolved_cells()
+        .filter(move |&(r, c)| board.candidates(r, c).contains(digit) && mask & bit(r, c) != 0)
+}
+
+/// Cell to report for a digit without templates: one of its candidates, or
+/// else the first unsolved cell.
+fn stuck_cell(board: &Board, digit: Digit) -> (usize, usize) {
+    cells_with(board, digit, !0)
+        .chain(board.unsolved_cells())
+        .next()
+        .unwrap_or_default()
+}
+
+/// Remove the digit from every cell outside all of the templates.
+fn eliminate_outside(
+    board: &mut Board,
+    digit: Digit,
+    templates: &[Template],
+) -> Result<bool, SolverError> {
+    let covered = templates.iter().fold(0, |acc, &t| acc | t);
+    let found: Vec<_> = cells_with(board, digit, !covered)
+        .map(|(r, c)| (r, c, digit))
+        .collect();
+    eliminate_candidates(board, found)
+}
+
+/// All templates of the digit that agree with the board, built row by row.
+fn templates_for(board: &Board, digit: Digit) -> Vec<Template> {
+    let options: Vec<Vec<usize>> = (0..9)
+        .map(|r| match (0..9).find(|&c| board.get(r, c) == Some(digit)) {
+            Some(c) => vec![c],
+            None => (0..9)
+                .filter(|&c| board.get(r, c).is_none() && board.candidates(r, c).contains(digit))
+                .collect(),
+        })
+        .collect();
+    let mut found = Vec::new();
+    extend_template(&options, 0, (0, 0), 0, &mut found);
+    found
+}
+
+fn extend_template(
+    options: &[Vec<usize>],
+    row: usize,
+    used: (u16, u16),
+    template: Template,
+    found: &mut Vec<Template>,
+) {
+    let Some(cols) = options.get(row) else {
+        found.push(template);
+        return;
+    };
+    let (used_cols, used_boxes) = used;
+    for &c in cols {
+        let boxed = row / 3 * 3 + c / 3;
+        if used_cols & (1 << c) != 0 || used_boxes & (1 << boxed) != 0 {
+            continue;
+        }
+        extend_template(
+            options,
+            row + 1,
+            (used_cols | (1 << c), used_boxes | (1 << boxed)),
+            template | bit(row, c),
+            found,
+        );
+    }
+}
@@ -1,7 +1,7 @@
+use super::coloring::{color_components, conjugate_pairs};
 use crate::SolverError;
-use crate::board::{self, Board};
-use crate::strategy::{Strategy, StrategyKind};
-use std::collections::{HashMap, HashSet, VecDeque};
+use crate::board::{self, Board, Unit};
+use crate::strategy::{Strategy, StrategyKind, eliminate_candidates_in};
 
 pub struct SimpleColoring;
 
@@ -13,94 +13,27 @@ impl Strategy for SimpleColoring {
     fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
         let mut changed = false;
         for digit in board::digits() {
-            let mut adjacency: HashMap<(usize, usize), Vec<(usize, usize)>> = HashMap::new();
-            for r in board::row_indices() {
-                let pos = board.row_candidate_positions(r, digit);
-                if pos.len() == 2 {
-                    let mut it = pos.iter();
-                    let c1 = it.next().unwrap();
-                    let c2 = it.next().unwrap();
-                    adjacency.entry((r, c1)).or_default().push((r, c2));
-                    adjacency.entry((r, c2)).or_default().push((r, c1));
-                }
-            }
-            for c in board::col_indices() {
-                let pos = board.col_candidate_positions(c, digit);
-                if pos.len() == 2 {
-                    let mut it = pos.iter();
-                    let r1 = it.next().unwrap();
-                    let r2 = it.next().unwrap();
-                    adjacency.entry((r1, c)).or_default().push((r2, c));
-                    adjacency.entry((r2, c)).or_default().push((r1, c));
-                }
-            }
-            for (br, bc) in board::box_indices() {
-                let coords = board.candidate_coords(board::Unit::Box(br, bc), digit);
-                if coords.len() == 2 {
-                    let mut it = coords.iter();
-                    let (r1, c1) = it.next().unwrap();
-                    let (r2, c2) = it.next().unwrap();
-                    adjacency.entry((r1, c1)).or_default().push((r2, c2));
-                    adjacency.entry((r2, c2)).or_default().push((r1, c1));
-                }
-            }
+            let adjacency = conjugate_pairs(board, digit);
+            for component in color_components(&adjacency) {
+                let color_sets = component.color_sets();
 
-            let mut visited: HashSet<(usize, usize)> = HashSet::new();
-            for &(r, c) in adjacency.keys() {
-                if visited.contains(&(r, c)) {
-                    continue;
-                }
-                let mut component = HashMap::new();
-                let mut queue = VecDeque::new();
-                component.insert((r, c), false);
-                queue.push_back((r, c));
-                visited.insert((r, c));
-                while let Some((cr, cc)) = queue.pop_front() {
-                    let color = *component.get(&(cr, cc)).unwrap();
-                    if let Some(neigh) = adjacency.get(&(cr, cc)) {
-                        for &(nr, nc) in neigh {
-                            if let std::collections::hash_map::Entry::Vacant(e) =
-                                component.entry((nr, nc))
-                            {
-                                e.insert(!color);
-                                visited.insert((nr, nc));
-                                queue.push_back((nr, nc));
-                            }
-                        }
-                    }
-                }
-
-                let mut color_sets = [HashSet::new(), HashSet::new()];
-                for (coord, &color) in &component {
-                    color_sets[color as usize].insert(*coord);
-                }
-
-                let mut invalid = [false, false];
-                for color in [false, true] {
-                    for &(cr, cc) in &color_sets[color as usize] {
-                        for peer in board.peer_coords(cr, cc) {
-                            if color_sets[color as usize].contains(&peer) {
-                                invalid[color as usize] = true;
-                                break;
-                            }
-                        }
-                        if invalid[color as usize] {
-                            break;
-                        }
-                    }
-                }
-
-                for color in [false, true] {
-                    if invalid[color as usize] {
-                        for &(cr, cc) in &color_sets[color as usize] {
-                            if let Some(res) = board.eliminate_candidate(cr, cc, digit) {
-                                if res {
-                                    changed = true;
-                                }
-                            } else {
-                                return Err(SolverError::Contradiction { row: cr, col: cc });
-                            }
-                        }
+                for set in &color_sets {
+                    // two cells of the color seeing each other rule it out,
+                    // looked for in board order so the same ones are reported
+                    let mut cells: Vec<_> = set.iter().copied().collect();
+                    cells.sort_unstable();
+                    let conflict = cells.iter().find_map(|&cell| {
+                        let peers = board.peer_coords(cell.0, cell.1);
+                        peers
+                            .into_iter()
+                            .find(|peer| set.contains(peer))
+                            .map(|peer| [cell, peer])
+                    });
+                    if let Some([a, b]) = conflict {
+                        let unit =
+                            Unit::all().find(|u| u.contains(a.0, a.1) && u.contains(b.0, b.1));
+                        let found = cells.iter().map(|&(r, c)| (r, c, digit));
+                        changed |= eliminate_candidates_in(board, unit, &[a, b], found)?;
                     }
                 }
 
@@ -110,23 +43,19 @@ impl Strategy for SimpleColoring {
                         {
                             continue;
                         }
-                        if component.contains_key(&(r0, c0)) {
+                        if component.colors.contains_key(&(r0, c0)) {
                             continue;
                         }
-                        let mut seen = [false, false];
+                        // a peer of each color, one of which holds the digit
+                        let mut seen = [None, None];
                         for peer in board.peer_coords(r0, c0) {
-                            if let Some(&color) = component.get(&peer) {
-                                seen[color as usize] = true;
+                            if let Some(&color) = component.colors.get(&peer) {
+                                seen[color as usize].get_or_insert(peer);
                             }
                         }
-                        if seen[0] && seen[1] {
-                            if let Some(res) = board.eliminate_candidate(r0, c0, digit) {
-                                if res {
-                                    changed = true;
-                                }
-                            } else {
-                                return Err(SolverError::Contradiction { row: r0, col: c0 });
-                            }
+                        if let [Some(a), Some(b)] = seen {
+                            let found = [(r0, c0, digit)];
+                            changed |= eliminate_candidates_in(board, None, &[a, b], found)?;
                         }
                     }
                 }
@@ -0,0 +1,111 @@
+use crate::SolverError;
+use crate::board::{self, Board, CandidateSet, Digit, Unit};
+use crate::strategy::{Strategy, StrategyKind, eliminate_candidates, subsets, union_of};
+
+type Coord = (usize, usize);
+type Cells = Vec<(Coord, CandidateSet)>;
+
+/// Sue de Coq (two-sector disjoint subsets).
+///
+/// The unsolved cells `C` where a box meets a row or column hold at least two
+/// more candidates `V` than cells. If cells `D` in the rest of the line and
+/// cells `E` in the rest of the box only hold digits of `V`, their digits are
+/// disjoint and `|C| + |D| + |E| == |V|`, then `C`, `D` and `E` together lock
+/// all of `V`: the line digits leave the rest of the line and the box digits
+/// leave the rest of the box.
+pub struct SueDeCoq;
+
+impl Strategy for SueDeCoq {
+    fn kind(&self) -> StrategyKind {
+        StrategyKind::SueDeCoq
+    }
+
+    fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
+        for (br, bc) in board::box_indices() {
+            let boxed = Unit::Box(br, bc);
+            let lines = (br..br + 3)
+                .map(Unit::Row)
+                .chain((bc..bc + 3).map(Unit::Col));
+            for line in lines {
+                let found = search_intersection(board, boxed, line);
+                if eliminate_candidates(board, found)? {
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+}
+
+fn search_intersection(board: &Board, boxed: Unit, line: Unit) -> Vec<(usize, usize, Digit)> {
+    let (intersection, line_rest): (Cells, Cells) = board
+        .unsolved_in_unit(line)
+        .partition(|&((r, c), _)| boxed.contains(r, c));
+    let box_rest: Cells = board
+        .unsolved_in_unit(boxed)
+        .filter(|&((r, c), _)| !line.contains(r, c))
+        .collect();
+
+    for cells in subsets(&intersection, 2, intersection.len()) {
+        let v = union_of(&cells);
+        if v.len() < cells.len() + 2 {
+            continue;
+        }
+        let line_sets = subsets_within(&line_rest, v);
+        let box_sets = subsets_within(&box_rest, v);
+        for d_cells in &line_sets {
+            let vd = union_of(d_cells);
+            for e_cells in &box_sets {
+                let ve = union_of(e_cells);
+                if !vd.intersection(ve).is_empty()
+                    || cells.len() + d_cells.len() + e_cells.len() != v.len()
+                {
+                    continue;
+                }
+                let line_digits = v.difference(ve);
+                let box_digits = v.difference(vd);
+                let used: Vec<Coord> = cells
+                    .iter()
+                    .chain(d_cells)
+                    .chain(e_cells)
+                    .map(|&(coord, _)| coord)
+                    .collect();
+                let found: Vec<_> = eliminations(&line_rest, &used, line_digits)
+                    .into_iter()
+                    .chain(eliminations(&box_rest, &used, box_digits))
+                    .chain(eliminations(&intersection, &used, v))
+                    .collect();
+                if !found.is_empty() {
+                    return found;
+                }
+            }
+        }
+    }
+    Vec::new()
+}
+
+/// Non-empty subsets of the cells holding only digits of `digits`, each
+/// contributing at least one of them.
+fn subsets_within(cells: &[(Coord, CandidateSet)], digits: CandidateSet) -> Vec<Cells> {
+    let inside: Cells = cells
+        .iter()
+        .copied()
+        .filter(|&(_, cands)| cands.difference(digits).is_empty())
+        .collect();
+    subsets(&inside, 1, digits.len())
+        .into_iter()
+        .filter(|set| union_of(set).len() > set.len())
+        .collect()
+}
+
+fn eliminations(
+    cells: &[(Coord, CandidateSet)],
+    used: &[Coord],
+    digits: CandidateSet,
+) -> Vec<(usize, usize, Digit)> {
+    cells
+        .iter()
+        .filter(|(coord, _)| !used.contains(coord))
+        .flat_map(|&((r, c), cands)| cands.intersection(digits).iter().map(move |d| (r, c, d)))
+        .collect()
+}
@@ -1,6 +1,6 @@
 use crate::SolverError;
-use crate::board::{self, Board};
-use crate::strategy::{Strategy, StrategyKind};
+use crate::board::{self, Board, Digit, Unit};
+use crate::strategy::{Strategy, StrategyKind, eliminate_candidates_in};
 
 const FISH_LEN: usize = 3;
 
@@ -25,22 +25,13 @@ impl Strategy for Swordfish {
                         union.extend(cols1.iter());
                         union.extend(cols2.iter());
                         union.extend(cols3.iter());
-                        if cols1.len() <= FISH_LEN
-                            && cols2.len() <= FISH_LEN
-                            && cols3.len() <= FISH_LEN
+                        if (1..=FISH_LEN).contains(&cols1.len())
+                            && (1..=FISH_LEN).contains(&cols2.len())
+                            && (1..=FISH_LEN).contains(&cols3.len())
                             && union.len() == FISH_LEN
                         {
-                            let changed = board::row_indices()
-                                .filter(|&r| r != r1 && r != r2 && r != r3)
-                                .flat_map(|r| union.iter().map(move |&c| (r, c)))
-                                .try_fold(false, |acc, (r, c)| {
-                                    match board.eliminate_candidate(r, c, digit) {
-                                        Some(true) => Ok(true),
-                                        Some(false) => Ok(acc),
-                                        None => Err(SolverError::Contradiction { row: r, col: c }),
-                                    }
-                                })?;
-                            if changed {
+                            let cover: Vec<_> = union.into_iter().collect();
+                            if eliminate_from_cover(board, digit, &[r1, r2, r3], &cover, false)? {
                                 return Ok(true);
                             }
                         }
@@ -62,22 +53,13 @@ impl Strategy for Swordfish {
                         union.extend(rows1.iter());
                         union.extend(rows2.iter());
                         union.extend(rows3.iter());
-                        if rows1.len() <= FISH_LEN
-                            && rows2.len() <= FISH_LEN
-                            && rows3.len() <= FISH_LEN
+                        if (1..=FISH_LEN).contains(&rows1.len())
+                            && (1..=FISH_LEN).contains(&rows2.len())
+                            && (1..=FISH_LEN).contains(&rows3.len())
                             && union.len() == FISH_LEN
                         {
-                            let changed = board::col_indices()
-                                .filter(|&c| c != c1 && c != c2 && c != c3)
-                                .flat_map(|c| union.iter().map(move |&r| (r, c)))
-                                .try_fold(false, |acc, (r, c)| {
-                                    match board.eliminate_candidate(r, c, digit) {
-                                        Some(true) => Ok(true),
-                                        Some(false) => Ok(acc),
-                                        None => Err(SolverError::Contradiction { row: r, col: c }),
-                                    }
-                                })?;
-                            if changed {
+                            let cover: Vec<_> = union.into_iter().collect();
+                            if eliminate_from_cover(board, digit, &[c1, c2, c3], &cover, true)? {
                                 return Ok(true);
                             }
                         }
@@ -89,3 +71,43 @@ impl Strategy for Swordfish {
         Ok(false)
     }
 }
+
+/// Remove the digit from the cover lines of a fish outside its base lines.
+/// The base lines are rows and the cover lines columns, or the other way
+/// round when `transposed`. A cell left without candidates is reported with
+/// its cover line and the base cells of the fish in it.
+pub(crate) fn eliminate_from_cover(
+    board: &mut Board,
+    digit: Digit,
+    base: &[usize],
+    cover: &[usize],
+    transposed: bool,
+) -> Result<bool, SolverError> {
+    let coord = |base: usize, cover: usize| {
+        if transposed {
+            (cover, base)
+        } else {
+            (base, cover)
+        }
+    };
+    let mut changed = false;
+    for &line in cover {
+        let unit = if transposed {
+            Unit::Row(line)
+        } else {
+            Unit::Col(line)
+        };
+        let cells: Vec<_> = base
+            .iter()
+            .map(|&b| coord(b, line))
+            .filter(|&(r, c)| board.candidates(r, c).contains(digit))
+            .collect();
+        let targets: Vec<_> = (0..9)
+            .filter(|i| !base.contains(i))
+            .map(|i| coord(i, line))
+            .map(|(r, c)| (r, c, digit))
+            .collect();
+        changed |= eliminate_candidates_in(board, Some(unit), &cells, targets)?;
+    }
+    Ok(changed)
+}
@@ -0,0 +1,127 @@
+use super::coloring::{Adjacency, Component, color_components, conjugate_pairs, link};
+use crate::SolverError;
+use crate::board::{self, Board, Digit};
+use crate::strategy::{Strategy, StrategyKind, eliminate_candidates};
+use std::collections::HashSet;
+
+const PAIR_LEN: usize = 2;
+
+/// A single candidate: the digit `.2` in the cell at row `.0`, column `.1`.
+type Node = (usize, usize, Digit);
+
+/// 3D Medusa extends simple coloring to every digit at once by also
+/// following the strong link between the two candidates of a bivalue cell.
+pub struct ThreeDMedusa;
+
+impl Strategy for ThreeDMedusa {
+    fn kind(&self) -> StrategyKind {
+        StrategyKind::ThreeDMedusa
+    }
+
+    fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
+        let adjacency = medusa_graph(board);
+        for component in color_components(&adjacency) {
+            let eliminations = match false_color(board, &component) {
+                Some(color) => component.color_sets()[color as usize]
+                    .iter()
+                    .copied()
+                    .collect(),
+                None => uncolored_eliminations(board, &component),
+            };
+            if eliminate_candidates(board, eliminations)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// Strong links between candidates: conjugate pairs of every digit plus the
+/// two candidates of every bivalue cell.
+fn medusa_graph(board: &Board) -> Adjacency<Node> {
+    let mut adjacency = Adjacency::new();
+    for digit in board::digits() {
+        for (&(r1, c1), neighbours) in &conjugate_pairs(board, digit) {
+            for &(r2, c2) in neighbours {
+                if (r1, c1) < (r2, c2) {
+                    link(&mut adjacency, (r1, c1, digit), (r2, c2, digit));
+                }
+            }
+        }
+    }
+    for (r, c) in board.unsolved_cells() {
+        let cands = board.candidates(r, c);
+        if cands.len() == PAIR_LEN {
+            let mut it = cands.iter();
+            let a = it.next().unwrap();
+            let b = it.next().unwrap();
+            link(&mut adjacency, (r, c, a), (r, c, b));
+        }
+    }
+    adjacency
+}
+
+/// Check whether a digit in a cell sees a candidate of the given color.
+fn sees_color(
+    board: &Board,
+    colored: &HashSet<Node>,
+    (r, c): (usize, usize),
+    digit: Digit,
+) -> bool {
+    board
+        .peer_coords(r, c)
+        .into_iter()
+        .any(|(pr, pc)| colored.contains(&(pr, pc, digit)))
+}
+
+/// Apply the contradiction rules and return the color that cannot be true:
+/// a color appearing twice in a cell, twice for one digit in a unit, or
+/// a color that would empty an uncolored cell.
+fn false_color(board: &Board, component: &Component<Node>) -> Option<bool> {
+    let sets = component.color_sets();
+    [false, true].into_iter().find(|&color| {
+        let set = &sets[color as usize];
+        let twice_in_cell = set.iter().any(|&(r, c, d)| {
+            set.iter()
+                .any(|&(r2, c2, d2)| (r, c) == (r2, c2) && d != d2)
+        });
+        let twice_in_unit = set
+            .iter()
+            .any(|&(r, c, d)| sees_color(board, set, (r, c), d));
+        let empties_cell = board.unsolved_cells().any(|(r, c)| {
+            let cands = board.candidates(r, c);
+            !cands.is_empty()
+                && cands.iter().all(|d| {
+                    !component.colors.contains_key(&(r, c, d)) && sees_color(board, set, (r, c), d)
+                })
+        });
+        twice_in_cell || twice_in_unit || empties_cell
+    })
+}
+
+/// Collect the uncolored candidates that can be removed because they would
+/// be false whichever color turns out to be true.
+fn uncolored_eliminations(board: &Board, component: &Component<Node>) -> Vec<Node> {
+    let sets = component.color_sets();
+    let mut eliminations = Vec::new();
+    for (r, c) in board.unsolved_cells() {
+        let cands = board.candidates(r, c);
+        let cell_colors: Vec<bool> = cands
+            .iter()
+            .filter_map(|d| component.colors.get(&(r, c, d)).copied())
+            .collect();
+        let cell_has_both = cell_colors.contains(&false) && cell_colors.contains(&true);
+        for d in cands.iter() {
+            if component.colors.contains_key(&(r, c, d)) {
+                continue;
+            }
+            let sees =
+                [false, true].map(|color| sees_color(board, &sets[color as usize], (r, c), d));
+            let sees_opposite_of_cell = cell_colors.iter().any(|&color| sees[!color as usize]);
+            if cell_has_both || (sees[0] && sees[1]) || sees_opposite_of_cell {
+                eliminations.push((r, c, d));
+            }
+        }
+    }
+    eliminations
+}
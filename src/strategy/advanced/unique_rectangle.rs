@@ -1,7 +1,133 @@
+//! Uniqueness strategies built on the deadly pattern of four cells at the
+//! corners of a rectangle spanning exactly two boxes. If such a pattern could
+//! be completed with only two digits the puzzle would have two solutions, so
+//! each strategy removes the candidates that would lead to it.
+
 use crate::SolverError;
-use crate::board::{self, Board};
-use crate::strategy::{Strategy, StrategyKind};
+use crate::board::{self, Board, CandidateSet, Digit, Unit};
+use crate::strategy::{Strategy, StrategyKind, eliminate_candidates, subsets_where, union_of};
+
+/// Corners are indexed row-major: 0 and 1 share the first row, 0 and 2 the
+/// first column, and `3 - i` is diagonal to corner `i`.
+const CORNERS: usize = 4;
+
+type Coord = (usize, usize);
+
+/// Rectangle whose unsolved corners all contain both rectangle digits.
+struct Rectangle {
+    corners: [Coord; CORNERS],
+    cands: [CandidateSet; CORNERS],
+    digits: [Digit; 2],
+}
+
+impl Rectangle {
+    fn pair(&self) -> CandidateSet {
+        let mut pair = CandidateSet::empty();
+        pair.insert(self.digits[0]);
+        pair.insert(self.digits[1]);
+        pair
+    }
+
+    /// Corners holding nothing but the rectangle digits.
+    fn floors(&self) -> Vec<usize> {
+        (0..CORNERS)
+            .filter(|&i| self.cands[i] == self.pair())
+            .collect()
+    }
+
+    /// Corners holding extra candidates besides the rectangle digits.
+    fn roofs(&self) -> Vec<usize> {
+        (0..CORNERS)
+            .filter(|&i| self.cands[i] != self.pair())
+            .collect()
+    }
+
+    fn extras(&self, i: usize) -> CandidateSet {
+        self.cands[i].difference(self.pair())
+    }
+
+    fn coords(&self, indices: &[usize]) -> Vec<Coord> {
+        indices.iter().map(|&i| self.corners[i]).collect()
+    }
+
+    /// Units containing both corners, which must share a row or column.
+    fn shared_units(&self, i: usize, j: usize) -> Vec<Unit> {
+        let (r1, c1) = self.corners[i];
+        let (r2, c2) = self.corners[j];
+        let line = if r1 == r2 {
+            Unit::Row(r1)
+        } else {
+            Unit::Col(c1)
+        };
+        let same_box = r1 / 3 == r2 / 3 && c1 / 3 == c2 / 3;
+        let mut units = vec![line];
+        if same_box {
+            units.push(Unit::Box(r1 / 3 * 3, c1 / 3 * 3));
+        }
+        units
+    }
+}
+
+fn share_line(i: usize, j: usize) -> bool {
+    i / 2 == j / 2 || i % 2 == j % 2
+}
+
+/// Rows and columns forming rectangles that span exactly two boxes.
+fn rectangle_corners() -> impl Iterator<Item = [Coord; CORNERS]> {
+    board::row_pairs().flat_map(|(r1, r2)| {
+        board::col_pairs()
+            .filter(move |&(c1, c2)| (r1 / 3 == r2 / 3) != (c1 / 3 == c2 / 3))
+            .map(move |(c1, c2)| [(r1, c1), (r1, c2), (r2, c1), (r2, c2)])
+    })
+}
+
+fn rectangles(board: &Board) -> Vec<Rectangle> {
+    let mut found = Vec::new();
+    for corners in rectangle_corners() {
+        if corners.iter().any(|&(r, c)| board.get(r, c).is_some()) {
+            continue;
+        }
+        let cands = corners.map(|(r, c)| board.candidates(r, c));
+        let common = cands
+            .iter()
+            .fold(CandidateSet::full(), |acc, &set| acc.intersection(set));
+        let digits: Vec<Digit> = common.iter().collect();
+        for (i, &a) in digits.iter().enumerate() {
+            for &b in &digits[i + 1..] {
+                found.push(Rectangle {
+                    corners,
+                    cands,
+                    digits: [a, b],
+                });
+            }
+        }
+    }
+    found
+}
 
+/// Try every rectangle in turn until one of them removes a candidate.
+fn apply_to_rectangles<F>(board: &mut Board, eliminations: F) -> Result<bool, SolverError>
+where
+    F: Fn(&Board, &Rectangle) -> Vec<(usize, usize, Digit)>,
+{
+    for rect in rectangles(board) {
+        let found = eliminations(board, &rect);
+        if eliminate_candidates(board, found)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn remove_from_cells(cells: &[Coord], digits: CandidateSet) -> Vec<(usize, usize, Digit)> {
+    cells
+        .iter()
+        .flat_map(|&(r, c)| digits.iter().map(move |d| (r, c, d)))
+        .collect()
+}
+
+/// Type 1: three corners hold only the rectangle digits, so the fourth must
+/// be one of its extra candidates.
 pub struct UniqueRectangle;
 
 impl Strategy for UniqueRectangle {
@@ -10,62 +136,319 @@ impl Strategy for UniqueRectangle {
     }
 
     fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
-        for a in board::digits() {
-            for b in board::digits() {
-                if a >= b {
-                    continue;
-                }
-                for (r1, r2) in board::row_pairs() {
-                    for (c1, c2) in board::col_pairs() {
-                        let coords = [(r1, c1), (r1, c2), (r2, c1), (r2, c2)];
-                        let mut base_count = 0;
-                        let mut extra_cell = None;
-                        for &(r, c) in &coords {
-                            let cands = board.candidates(r, c);
-                            if cands.is_empty() {
-                                base_count = 0;
-                                break;
-                            }
-                            if cands == vec![a, b] || cands == vec![b, a] {
-                                base_count += 1;
-                            } else if cands.contains(a) && cands.contains(b) {
-                                if extra_cell.is_none() {
-                                    extra_cell = Some((r, c, cands));
-                                } else {
-                                    base_count = 0;
-                                    break;
-                                }
-                            } else {
-                                base_count = 0;
-                                break;
-                            }
-                        }
-                        if base_count == 3 {
-                            if let Some((er, ec, cands)) = extra_cell {
-                                let mut changed = false;
-                                for d in cands.iter() {
-                                    if d != a && d != b {
-                                        if let Some(res) = board.eliminate_candidate(er, ec, d) {
-                                            if res {
-                                                changed = true;
-                                            }
-                                        } else {
-                                            return Err(SolverError::Contradiction {
-                                                row: er,
-                                                col: ec,
-                                            });
-                                        }
-                                    }
-                                }
-                                if changed {
-                                    return Ok(true);
-                                }
-                            }
-                        }
+        apply_to_rectangles(board, |_, rect| match rect.roofs()[..] {
+            [roof] => remove_from_cells(&rect.coords(&[roof]), rect.pair()),
+            _ => Vec::new(),
+        })
+    }
+}
+
+/// Roof corners that all have the same single extra candidate.
+fn single_extra(rect: &Rectangle, roofs: &[usize]) -> Option<Digit> {
+    let extra = rect.extras(roofs[0]);
+    (extra.len() == 1 && roofs.iter().all(|&i| rect.extras(i) == extra))
+        .then(|| extra.iter().next().unwrap())
+}
+
+fn single_extra_eliminations(board: &Board, rect: &Rectangle) -> Vec<(usize, usize, Digit)> {
+    let roofs = rect.roofs();
+    match single_extra(rect, &roofs) {
+        Some(x) => board
+            .common_peers(&rect.coords(&roofs))
+            .into_iter()
+            .map(|(r, c)| (r, c, x))
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Type 2: two roof corners in one line share a single extra candidate, which
+/// must be placed in one of them.
+pub struct UniqueRectangleType2;
+
+impl Strategy for UniqueRectangleType2 {
+    fn kind(&self) -> StrategyKind {
+        StrategyKind::UniqueRectangleType2
+    }
+
+    fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
+        apply_to_rectangles(board, |b, rect| match rect.roofs()[..] {
+            [i, j] if share_line(i, j) => single_extra_eliminations(b, rect),
+            _ => Vec::new(),
+        })
+    }
+}
+
+/// Type 3: the extra candidates of two roof corners in one line act as a
+/// single virtual cell that forms a naked subset with other cells of a shared
+/// unit.
+pub struct UniqueRectangleType3;
+
+/// Largest number of real cells joining the virtual cell in a naked subset.
+const MAX_SUBSET: usize = 3;
+
+impl Strategy for UniqueRectangleType3 {
+    fn kind(&self) -> StrategyKind {
+        StrategyKind::UniqueRectangleType3
+    }
+
+    fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
+        apply_to_rectangles(board, |b, rect| match rect.roofs()[..] {
+            [i, j] if share_line(i, j) => {
+                let virtual_cell = rect.extras(i).union(rect.extras(j));
+                let roofs = rect.coords(&[i, j]);
+                rect.shared_units(i, j)
+                    .into_iter()
+                    .map(|unit| naked_subset_with(b, unit, &roofs, virtual_cell))
+                    .find(|found| !found.is_empty())
+                    .unwrap_or_default()
+            }
+            _ => Vec::new(),
+        })
+    }
+}
+
+/// Look for cells in the unit that together with the virtual cell hold one
+/// more digit than cells, and eliminate those digits from the rest of the unit.
+fn naked_subset_with(
+    board: &Board,
+    unit: Unit,
+    roofs: &[Coord],
+    virtual_cell: CandidateSet,
+) -> Vec<(usize, usize, Digit)> {
+    let others: Vec<(Coord, CandidateSet)> = board
+        .unsolved_in_unit(unit)
+        .filter(|(coord, _)| !roofs.contains(coord))
+        .collect();
+    let digits_with = |set: &[(Coord, CandidateSet)]| virtual_cell.union(union_of(set));
+    subsets_where(&others, 1, MAX_SUBSET, |set| {
+        digits_with(set).len() <= MAX_SUBSET + 1
+    })
+    .into_iter()
+    .filter(|set| digits_with(set).len() == set.len() + 1)
+    .map(|set| {
+        let digits = digits_with(&set);
+        others
+            .iter()
+            .filter(|cell| !set.contains(cell))
+            .flat_map(|&((r, c), cands)| cands.intersection(digits).iter().map(move |d| (r, c, d)))
+            .collect::<Vec<_>>()
+    })
+    .find(|eliminations| !eliminations.is_empty())
+    .unwrap_or_default()
+}
+
+/// Type 4: one rectangle digit is locked to the two roof corners within a
+/// shared unit, so the other rectangle digit can be removed from both.
+pub struct UniqueRectangleType4;
+
+impl Strategy for UniqueRectangleType4 {
+    fn kind(&self) -> StrategyKind {
+        StrategyKind::UniqueRectangleType4
+    }
+
+    fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
+        apply_to_rectangles(board, |b, rect| match rect.roofs()[..] {
+            [i, j] if share_line(i, j) => {
+                let roofs = rect.coords(&[i, j]);
+                let locked = rect.shared_units(i, j).into_iter().find_map(|unit| {
+                    rect.digits.into_iter().find(|&d| {
+                        let coords = b.candidate_coords(unit, d);
+                        coords.len() == 2 && coords.iter().all(|p| roofs.contains(&p))
+                    })
+                });
+                match locked {
+                    Some(d) => {
+                        let mut other = rect.pair();
+                        other.remove(d);
+                        remove_from_cells(&roofs, other)
                     }
+                    None => Vec::new(),
                 }
             }
+            _ => Vec::new(),
+        })
+    }
+}
+
+/// Type 5: two diagonal or three roof corners share a single extra candidate,
+/// which is removed from every cell seeing all of them.
+pub struct UniqueRectangleType5;
+
+impl Strategy for UniqueRectangleType5 {
+    fn kind(&self) -> StrategyKind {
+        StrategyKind::UniqueRectangleType5
+    }
+
+    fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
+        apply_to_rectangles(board, |b, rect| match rect.roofs()[..] {
+            [i, j] if !share_line(i, j) => single_extra_eliminations(b, rect),
+            [_, _, _] => single_extra_eliminations(b, rect),
+            _ => Vec::new(),
+        })
+    }
+}
+
+/// Type 6: with diagonal roof corners, a rectangle digit forming an X-Wing on
+/// the rectangle must sit on the floor diagonal and leaves both roofs.
+pub struct UniqueRectangleType6;
+
+impl Strategy for UniqueRectangleType6 {
+    fn kind(&self) -> StrategyKind {
+        StrategyKind::UniqueRectangleType6
+    }
+
+    fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
+        apply_to_rectangles(board, |b, rect| match rect.roofs()[..] {
+            [i, j] if !share_line(i, j) => {
+                let roofs = rect.coords(&[i, j]);
+                rect.digits
+                    .into_iter()
+                    .filter(|&d| locked_in_rectangle(b, rect, d))
+                    .flat_map(|d| roofs.iter().map(move |&(r, c)| (r, c, d)))
+                    .collect()
+            }
+            _ => Vec::new(),
+        })
+    }
+}
+
+/// Check whether the digit appears only in the rectangle corners of both its
+/// rows or of both its columns.
+fn locked_in_rectangle(board: &Board, rect: &Rectangle, digit: Digit) -> bool {
+    let rows = [rect.corners[0].0, rect.corners[3].0];
+    let cols = [rect.corners[0].1, rect.corners[3].1];
+    let in_rows = rows.iter().all(|&r| {
+        let pos = board.row_candidate_positions(r, digit);
+        pos.len() == 2 && pos.iter().all(|c| cols.contains(&c))
+    });
+    let in_cols = cols.iter().all(|&c| {
+        let pos = board.col_candidate_positions(c, digit);
+        pos.len() == 2 && pos.iter().all(|r| rows.contains(&r))
+    });
+    in_rows || in_cols
+}
+
+/// Hidden rectangle: a floor corner whose diagonal corner has one rectangle
+/// digit locked to the rectangle in both its row and column cannot hold the
+/// other rectangle digit.
+pub struct HiddenRectangle;
+
+impl Strategy for HiddenRectangle {
+    fn kind(&self) -> StrategyKind {
+        StrategyKind::HiddenRectangle
+    }
+
+    fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
+        apply_to_rectangles(board, |b, rect| {
+            rect.floors()
+                .into_iter()
+                .flat_map(|floor| {
+                    let (r, c) = rect.corners[3 - floor];
+                    let (fr, fc) = rect.corners[floor];
+                    rect.digits.into_iter().filter_map(move |d| {
+                        let row = b.row_candidate_positions(r, d);
+                        let col = b.col_candidate_positions(c, d);
+                        let strong = row.len() == 2
+                            && row.iter().all(|cc| cc == c || cc == fc)
+                            && col.len() == 2
+                            && col.iter().all(|rr| rr == r || rr == fr);
+                        let other = rect.digits.into_iter().find(|&o| o != d).unwrap();
+                        strong.then_some((r, c, other))
+                    })
+                })
+                .collect()
+        })
+    }
+}
+
+/// Avoidable rectangle: corners solved while solving (not givens) could swap
+/// their digits with the remaining corners, so the deadly pattern must be
+/// avoided.
+pub struct AvoidableRectangle;
+
+impl Strategy for AvoidableRectangle {
+    fn kind(&self) -> StrategyKind {
+        StrategyKind::AvoidableRectangle
+    }
+
+    fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
+        for corners in rectangle_corners() {
+            if corners.iter().any(|&(r, c)| board.is_given(r, c)) {
+                continue;
+            }
+            let values = corners.map(|(r, c)| board.get(r, c));
+            let found = avoidable_type1(board, &corners, &values)
+                .into_iter()
+                .chain(avoidable_type2(board, &corners, &values))
+                .collect::<Vec<_>>();
+            if eliminate_candidates(board, found)? {
+                return Ok(true);
+            }
         }
         Ok(false)
     }
 }
+
+/// Three solved corners `a`, `b`, `b` leave the last corner unable to be `a`.
+fn avoidable_type1(
+    board: &Board,
+    corners: &[Coord; CORNERS],
+    values: &[Option<Digit>; CORNERS],
+) -> Vec<(usize, usize, Digit)> {
+    let open: Vec<usize> = (0..CORNERS).filter(|&i| values[i].is_none()).collect();
+    let [u] = open[..] else {
+        return Vec::new();
+    };
+    let neighbours: Vec<usize> = (0..CORNERS).filter(|&i| i != u && i != 3 - u).collect();
+    match (values[3 - u], values[neighbours[0]], values[neighbours[1]]) {
+        (Some(a), Some(b1), Some(b2)) if b1 == b2 && a != b1 => {
+            let (r, c) = corners[u];
+            if board.candidates(r, c).contains(a) {
+                vec![(r, c, a)]
+            } else {
+                Vec::new()
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Two solved corners `a` and `b` in one line leave the other two corners as
+/// `{b, x}` and `{a, x}`; one of them must be `x`.
+fn avoidable_type2(
+    board: &Board,
+    corners: &[Coord; CORNERS],
+    values: &[Option<Digit>; CORNERS],
+) -> Vec<(usize, usize, Digit)> {
+    let solved: Vec<usize> = (0..CORNERS).filter(|&i| values[i].is_some()).collect();
+    let [s1, s2] = solved[..] else {
+        return Vec::new();
+    };
+    if !share_line(s1, s2) {
+        return Vec::new();
+    }
+    let (a, b) = (values[s1].unwrap(), values[s2].unwrap());
+    // the open corner in line with s1 would take b, the other one a
+    let u1 = (0..CORNERS)
+        .find(|&i| i != s1 && i != s2 && share_line(i, s1))
+        .unwrap();
+    let u2 = 3 - s1;
+    let (c1, c2) = (
+        board.candidates(corners[u1].0, corners[u1].1),
+        board.candidates(corners[u2].0, corners[u2].1),
+    );
+    let (mut rest1, mut rest2) = (c1, c2);
+    rest1.remove(b);
+    rest2.remove(a);
+    if c1.len() == 2 && c2.len() == 2 && c1.contains(b) && c2.contains(a) && rest1 == rest2 {
+        let x = rest1.iter().next().unwrap();
+        board
+            .common_peers(&[corners[u1], corners[u2]])
+            .into_iter()
+            .map(|(r, c)| (r, c, x))
+            .collect()
+    } else {
+        Vec::new()
+    }
+}
@@ -26,7 +26,12 @@ impl Strategy for XWing {
                             match board.eliminate_candidate(r, c, digit) {
                                 Some(true) => Ok(true),
                                 Some(false) => Ok(changed),
-                                None => Err(SolverError::Contradiction { row: r, col: c }),
+                                None => {
+                                    let corners =
+                                        cols1.iter().flat_map(|c| [(r1, c), (r2, c)]).collect();
+                                    Err(SolverError::contradiction(r, c, Some(digit))
+                                        .in_unit(None, corners))
+                                }
                             }
                         })
                 })
@@ -51,7 +56,12 @@ impl Strategy for XWing {
                             match board.eliminate_candidate(r, c, digit) {
                                 Some(true) => Ok(true),
                                 Some(false) => Ok(changed),
-                                None => Err(SolverError::Contradiction { row: r, col: c }),
+                                None => {
+                                    let corners =
+                                        rows1.iter().flat_map(|r| [(r, c1), (r, c2)]).collect();
+                                    Err(SolverError::contradiction(r, c, Some(digit))
+                                        .in_unit(None, corners))
+                                }
                             }
                         })
                 })
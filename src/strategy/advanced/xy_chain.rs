@@ -1,11 +1,33 @@
 use crate::SolverError;
 use crate::board::Board;
-use crate::strategy::{Strategy, StrategyKind};
+use crate::strategy::{Strategy, StrategyKind, eliminate_candidates_in};
 use std::collections::{HashSet, VecDeque};
 
 const PAIR_LEN: usize = 2;
 
-pub struct XYChain;
+/// XY-Chain of bivalue cells, optionally limited to `max_length` cells.
+pub struct XYChain {
+    max_length: Option<usize>,
+}
+
+impl XYChain {
+    pub const fn new() -> Self {
+        Self { max_length: None }
+    }
+
+    /// Only follow chains of at most `max_length` cells.
+    pub const fn with_max_length(max_length: usize) -> Self {
+        Self {
+            max_length: Some(max_length),
+        }
+    }
+}
+
+impl Default for XYChain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 #[derive(Clone, Copy, Hash, PartialEq, Eq)]
 struct Node {
@@ -13,6 +35,7 @@ struct Node {
     c: usize,
     digit: u8,
     prev: u8,
+    len: usize,
 }
 
 impl Strategy for XYChain {
@@ -33,11 +56,14 @@ impl Strategy for XYChain {
 
             for &start_digit in &digits {
                 let other_digit = digits.iter().find(|&&d| d != start_digit).copied().unwrap();
+                // without `start_digit` the pivot holds the other digit, and a
+                // chain ending on `start_digit` proves one of its ends holds it
                 let start = Node {
                     r: r0,
                     c: c0,
-                    digit: start_digit,
-                    prev: other_digit,
+                    digit: other_digit,
+                    prev: start_digit,
+                    len: 1,
                 };
                 let mut queue = VecDeque::new();
                 queue.push_back(start);
@@ -45,6 +71,9 @@ impl Strategy for XYChain {
                 visited.insert((start.r, start.c, start.digit));
 
                 while let Some(node) = queue.pop_front() {
+                    if self.max_length.is_some_and(|max| node.len >= max) {
+                        continue;
+                    }
                     let peers = board.peer_coords(node.r, node.c);
                     for &(nr, nc) in &peers {
                         let cand = board.candidates(nr, nc);
@@ -58,19 +87,14 @@ impl Strategy for XYChain {
                             && peers0.contains(&(nr, nc))
                         {
                             let peers1 = board.peer_coords(nr, nc);
-                            let changed = peers0.iter().filter(|p| peers1.contains(p)).try_fold(
-                                false,
-                                |acc, &(rr, cc)| match board.eliminate_candidate(
-                                    rr,
-                                    cc,
-                                    start_digit,
-                                ) {
-                                    Some(true) => Ok(true),
-                                    Some(false) => Ok(acc),
-                                    None => Err(SolverError::Contradiction { row: rr, col: cc }),
-                                },
-                            )?;
-                            if changed {
+                            let found: Vec<_> = peers0
+                                .iter()
+                                .filter(|p| peers1.contains(p))
+                                .map(|&(rr, cc)| (rr, cc, start_digit))
+                                .collect();
+                            // the ends of the chain, one of which holds the digit
+                            let ends = [(r0, c0), (nr, nc)];
+                            if eliminate_candidates_in(board, None, &ends, found)? {
                                 return Ok(true);
                             }
                         }
@@ -81,6 +105,7 @@ impl Strategy for XYChain {
                                 c: nc,
                                 digit: next_digit,
                                 prev: node.digit,
+                                len: node.len + 1,
                             });
                         }
                     }
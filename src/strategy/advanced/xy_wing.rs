@@ -50,7 +50,8 @@ impl Strategy for XYWing {
                         |acc, &(rr, cc)| match board.eliminate_candidate(rr, cc, z) {
                             Some(true) => Ok(true),
                             Some(false) => Ok(acc),
-                            None => Err(SolverError::Contradiction { row: rr, col: cc }),
+                            None => Err(SolverError::contradiction(rr, cc, Some(z))
+                                .in_unit(None, vec![(r1, c1), (r2, c2)])),
                         },
                     )?;
                     if changed {
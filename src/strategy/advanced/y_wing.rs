@@ -68,7 +68,8 @@ impl Strategy for YWing {
                                 match board.eliminate_candidate(rr, cc, other) {
                                     Some(true) => Ok(true),
                                     Some(false) => Ok(changed),
-                                    None => Err(SolverError::Contradiction { row: rr, col: cc }),
+                                    None => Err(SolverError::contradiction(rr, cc, Some(other))
+                                        .in_unit(None, vec![(r1, c1), (r2, c2)])),
                                 }
                             })
                             .map_or_else(
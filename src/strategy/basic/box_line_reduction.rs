@@ -1,64 +1,15 @@
-use crate::SolverError;
-use crate::board::Board;
-use crate::strategy::{Strategy, StrategyKind};
+//! Former name of the box to line check. It always removed a digit pointing
+//! out of a box from the rest of its line, which is locked candidates type 1;
+//! the line to box direction is [`LockedCandidatesType2`]. The
+//! `box-line-reduction` strategy name parses to type 1 as well.
+//!
+//! [`LockedCandidatesType2`]: super::locked_candidates::LockedCandidatesType2
 
-pub struct BoxLineReduction;
+use super::locked_candidates::LockedCandidatesType1;
 
-impl Strategy for BoxLineReduction {
-    fn kind(&self) -> StrategyKind {
-        StrategyKind::BoxLineReduction
-    }
+#[deprecated(note = "use LockedCandidatesType1")]
+pub type BoxLineReduction = LockedCandidatesType1;
 
-    fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
-        board.try_for_each_box_digit_mut(|b, unit, digit| {
-            let mut positions = Vec::new();
-            b.for_each_in_unit(unit, |r, c, val| {
-                if val.is_none() && b.candidates(r, c).contains(digit) {
-                    positions.push((r, c));
-                }
-            });
-            if positions.len() <= 1 {
-                return Ok(false);
-            }
-
-            let same_row = positions.iter().all(|&(r, _)| r == positions[0].0);
-            let same_col = positions.iter().all(|&(_, c)| c == positions[0].1);
-
-            if same_row {
-                let row = positions[0].0;
-                let mut changed = false;
-                for c in 0..9 {
-                    if !unit.contains(row, c) {
-                        match b.eliminate_candidate(row, c, digit) {
-                            Some(true) => changed = true,
-                            None => return Err(SolverError::Contradiction { row, col: c }),
-                            _ => {}
-                        }
-                    }
-                }
-                if changed {
-                    return Ok(true);
-                }
-            }
-
-            if same_col {
-                let col = positions[0].1;
-                let mut changed = false;
-                for r in 0..9 {
-                    if !unit.contains(r, col) {
-                        match b.eliminate_candidate(r, col, digit) {
-                            Some(true) => changed = true,
-                            None => return Err(SolverError::Contradiction { row: r, col }),
-                            _ => {}
-                        }
-                    }
-                }
-                if changed {
-                    return Ok(true);
-                }
-            }
-
-            Ok(false)
-        })
-    }
-}
+#[deprecated(note = "use LockedCandidatesType1")]
+#[allow(non_upper_case_globals)]
+pub const BoxLineReduction: LockedCandidatesType1 = LockedCandidatesType1;
@@ -11,10 +11,8 @@ impl Strategy for HiddenPair {
 
     fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
         for unit in Unit::all() {
-            if let Some(changed) = find_hidden_pair_unit(board, unit)? {
-                if changed {
-                    return Ok(true);
-                }
+            if let Some(true) = find_hidden_pair_unit(board, unit)? {
+                return Ok(true);
             }
         }
         Ok(false)
@@ -40,7 +38,10 @@ fn find_hidden_pair_unit(board: &mut Board, unit: Unit) -> Result<Option<bool>,
                         if d != d1 && d != d2 {
                             match board.eliminate_candidate(r, c, d) {
                                 Some(true) => changed = true,
-                                None => return Err(SolverError::Contradiction { row: r, col: c }),
+                                None => {
+                                    return Err(SolverError::contradiction(r, c, Some(d))
+                                        .in_unit(Some(unit), positions[d1 as usize].clone()));
+                                }
                                 _ => {}
                             }
                         }
@@ -1,5 +1,5 @@
 use crate::SolverError;
-use crate::board::Board;
+use crate::board::{Board, Unit};
 use crate::strategy::{Strategy, StrategyKind};
 
 pub struct HiddenQuad;
@@ -40,7 +40,7 @@ fn search_row(board: &mut Board, r: usize) -> Result<bool, SolverError> {
             }
         }
     }
-    apply_hidden(positions, |idx| (r, idx), board)
+    apply_hidden(positions, Unit::Row(r), |idx| (r, idx), board)
 }
 
 fn search_col(board: &mut Board, c: usize) -> Result<bool, SolverError> {
@@ -52,7 +52,7 @@ fn search_col(board: &mut Board, c: usize) -> Result<bool, SolverError> {
             }
         }
     }
-    apply_hidden(positions, |idx| (idx, c), board)
+    apply_hidden(positions, Unit::Col(c), |idx| (idx, c), board)
 }
 
 fn search_box(board: &mut Board, start_r: usize, start_c: usize) -> Result<bool, SolverError> {
@@ -70,6 +70,13 @@ fn search_box(board: &mut Board, start_r: usize, start_c: usize) -> Result<bool,
         for d2 in d1 + 1..=7 {
             for d3 in d2 + 1..=8 {
                 for d4 in d3 + 1..=9 {
+                    // a digit already placed in the unit has no positions
+                    if [d1, d2, d3, d4]
+                        .iter()
+                        .any(|&d| positions[d as usize].is_empty())
+                    {
+                        continue;
+                    }
                     let union: Vec<(usize, usize)> = positions[d1 as usize]
                         .iter()
                         .chain(&positions[d2 as usize])
@@ -88,10 +95,8 @@ fn search_box(board: &mut Board, start_r: usize, start_c: usize) -> Result<bool,
                                     match board.eliminate_candidate(r, c, d) {
                                         Some(true) => changed = true,
                                         None => {
-                                            return Err(SolverError::Contradiction {
-                                                row: r,
-                                                col: c,
-                                            });
+                                            return Err(SolverError::contradiction(r, c, Some(d))
+                                                .in_unit(Some(Unit::Box(start_r, start_c)), uniq));
                                         }
                                         _ => {}
                                     }
@@ -111,6 +116,7 @@ fn search_box(board: &mut Board, start_r: usize, start_c: usize) -> Result<bool,
 
 fn apply_hidden<F>(
     positions: [Vec<usize>; 10],
+    unit: Unit,
     idx_to_coord: F,
     board: &mut Board,
 ) -> Result<bool, SolverError>
@@ -121,6 +127,13 @@ where
         for d2 in d1 + 1..=7 {
             for d3 in d2 + 1..=8 {
                 for d4 in d3 + 1..=9 {
+                    // a digit already placed in the unit has no positions
+                    if [d1, d2, d3, d4]
+                        .iter()
+                        .any(|&d| positions[d as usize].is_empty())
+                    {
+                        continue;
+                    }
                     let pos1 = &positions[d1 as usize];
                     let pos2 = &positions[d2 as usize];
                     let pos3 = &positions[d3 as usize];
@@ -150,10 +163,9 @@ where
                                     match board.eliminate_candidate(r, c, d) {
                                         Some(true) => changed = true,
                                         None => {
-                                            return Err(SolverError::Contradiction {
-                                                row: r,
-                                                col: c,
-                                            });
+                                            let subset = union.iter().map(|&i| idx_to_coord(i));
+                                            return Err(SolverError::contradiction(r, c, Some(d))
+                                                .in_unit(Some(unit), subset.collect()));
                                         }
                                         _ => {}
                                     }
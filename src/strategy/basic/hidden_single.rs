@@ -1,6 +1,6 @@
 use crate::SolverError;
 use crate::board::{Board, Digit, Unit};
-use crate::strategy::{Strategy, StrategyKind};
+use crate::strategy::{Deduction, Strategy, StrategyKind};
 use std::collections::HashMap;
 
 pub struct HiddenSingle;
@@ -19,9 +19,26 @@ impl Strategy for HiddenSingle {
         }
         Ok(false)
     }
+
+    fn deductions(&self, board: &Board) -> Option<Vec<Deduction>> {
+        let mut singles = Vec::new();
+        for unit in Unit::all() {
+            for (r, c, d) in hidden_in_unit(board, unit) {
+                let single = Deduction::Place(r, c, d);
+                if !singles.contains(&single) {
+                    singles.push(single);
+                }
+            }
+        }
+        Some(singles)
+    }
 }
 
 fn find_hidden_unit(board: &Board, unit: Unit) -> Option<(usize, usize, Digit)> {
+    hidden_in_unit(board, unit).next()
+}
+
+fn hidden_in_unit(board: &Board, unit: Unit) -> impl Iterator<Item = (usize, usize, Digit)> {
     let mut locs: HashMap<Digit, Vec<(usize, usize)>> = HashMap::new();
     board.for_each_in_unit(unit, |r, c, val| {
         if val.is_none() {
@@ -30,7 +47,7 @@ fn find_hidden_unit(board: &Board, unit: Unit) -> Option<(usize, usize, Digit)>
             }
         }
     });
-    locs.into_iter().find_map(|(d, pos)| {
+    locs.into_iter().filter_map(|(d, pos)| {
         (pos.len() == 1).then(|| {
             let (r, c) = pos[0];
             (r, c, d)
@@ -55,7 +55,8 @@ fn search_unit(board: &mut Board, unit: Unit) -> Result<bool, SolverError> {
                                 match board.eliminate_candidate(r, c, d) {
                                     Some(true) => changed = true,
                                     None => {
-                                        return Err(SolverError::Contradiction { row: r, col: c });
+                                        return Err(SolverError::contradiction(r, c, Some(d))
+                                            .in_unit(Some(unit), union.clone()));
                                     }
                                     _ => {}
                                 }
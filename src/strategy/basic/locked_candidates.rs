@@ -0,0 +1,106 @@
+use crate::SolverError;
+use crate::board::{self, Board, Digit, Unit};
+use crate::strategy::{Deduction, Strategy, StrategyKind, eliminate_candidates};
+
+/// Locked Candidates Type 1 (pointing): the two or three positions of a digit
+/// in a box share a row or column, so the digit leaves the rest of that line.
+pub struct LockedCandidatesType1;
+
+impl Strategy for LockedCandidatesType1 {
+    fn kind(&self) -> StrategyKind {
+        StrategyKind::LockedCandidatesType1
+    }
+
+    fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
+        apply_locked(board, Unit::boxes(), |(r, c)| {
+            vec![Unit::Row(r), Unit::Col(c)]
+        })
+    }
+
+    fn deductions(&self, board: &Board) -> Option<Vec<Deduction>> {
+        Some(all_locked(board, Unit::boxes(), |(r, c)| {
+            vec![Unit::Row(r), Unit::Col(c)]
+        }))
+    }
+}
+
+/// Locked Candidates Type 2 (claiming): the two or three positions of a digit
+/// in a row or column share a box, so the digit leaves the rest of that box.
+pub struct LockedCandidatesType2;
+
+impl Strategy for LockedCandidatesType2 {
+    fn kind(&self) -> StrategyKind {
+        StrategyKind::LockedCandidatesType2
+    }
+
+    fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
+        let lines = (0..9).map(Unit::Row).chain((0..9).map(Unit::Col));
+        apply_locked(board, lines, |(r, c)| vec![Unit::Box(r / 3 * 3, c / 3 * 3)])
+    }
+
+    fn deductions(&self, board: &Board) -> Option<Vec<Deduction>> {
+        let lines = (0..9).map(Unit::Row).chain((0..9).map(Unit::Col));
+        Some(all_locked(board, lines, |(r, c)| {
+            vec![Unit::Box(r / 3 * 3, c / 3 * 3)]
+        }))
+    }
+}
+
+/// For every digit confined to one of the `targets` of its first position
+/// within a unit, remove it from the rest of that target.
+fn apply_locked<U, F>(board: &mut Board, units: U, targets: F) -> Result<bool, SolverError>
+where
+    U: Iterator<Item = Unit>,
+    F: Fn((usize, usize)) -> Vec<Unit>,
+{
+    for unit in units {
+        for d in board::digits() {
+            let found = locked_in(board, unit, d, &targets);
+            if eliminate_candidates(board, found)? {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// Every elimination [`apply_locked`] could make on the board as it is.
+fn all_locked<U, F>(board: &Board, units: U, targets: F) -> Vec<Deduction>
+where
+    U: Iterator<Item = Unit>,
+    F: Fn((usize, usize)) -> Vec<Unit>,
+{
+    let mut found = Vec::new();
+    for unit in units {
+        for d in board::digits() {
+            for (r, c, d) in locked_in(board, unit, d, &targets) {
+                let deduction = Deduction::Eliminate(r, c, d);
+                if !found.contains(&deduction) {
+                    found.push(deduction);
+                }
+            }
+        }
+    }
+    found
+}
+
+fn locked_in<F>(board: &Board, unit: Unit, digit: Digit, targets: &F) -> Vec<(usize, usize, Digit)>
+where
+    F: Fn((usize, usize)) -> Vec<Unit>,
+{
+    let positions = board.candidate_coords(unit, digit);
+    let Some(first) = positions.into_iter().next() else {
+        return Vec::new();
+    };
+    if !(2..=3).contains(&positions.len()) {
+        return Vec::new();
+    }
+    targets(first)
+        .into_iter()
+        .filter(|target| positions.into_iter().all(|(r, c)| target.contains(r, c)))
+        .flat_map(|target| board.unit_iter(target))
+        .filter(|&(r, c)| !unit.contains(r, c))
+        .filter(|&(r, c)| board.get(r, c).is_none() && board.candidates(r, c).contains(digit))
+        .map(|(r, c)| (r, c, digit))
+        .collect()
+}
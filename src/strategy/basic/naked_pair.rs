@@ -49,7 +49,8 @@ fn search_unit(board: &mut Board, unit: Unit) -> Result<bool, SolverError> {
                             match board.eliminate_candidate(rr, cc, d) {
                                 Some(true) => changed = true,
                                 None => {
-                                    return Err(SolverError::Contradiction { row: rr, col: cc });
+                                    return Err(SolverError::contradiction(rr, cc, Some(d))
+                                        .in_unit(Some(unit), vec![cells[i].0, cells[j].0]));
                                 }
                                 _ => {}
                             }
@@ -48,10 +48,15 @@ fn search_unit(board: &mut Board, unit: Unit) -> Result<bool, SolverError> {
                                     match board.eliminate_candidate(r, cidx, digit) {
                                         Some(true) => changed = true,
                                         None => {
-                                            return Err(SolverError::Contradiction {
-                                                row: r,
-                                                col: cidx,
-                                            });
+                                            let subset = vec![
+                                                cells[a].0, cells[b].0, cells[c].0, cells[d].0,
+                                            ];
+                                            return Err(SolverError::contradiction(
+                                                r,
+                                                cidx,
+                                                Some(digit),
+                                            )
+                                            .in_unit(Some(unit), subset));
                                         }
                                         _ => {}
                                     }
@@ -41,7 +41,9 @@ fn search_unit(board: &mut Board, unit: Unit) -> Result<bool, SolverError> {
                                 match board.eliminate_candidate(r, c, d) {
                                     Some(true) => changed = true,
                                     None => {
-                                        return Err(SolverError::Contradiction { row: r, col: c });
+                                        let subset = vec![cells[i].0, cells[j].0, cells[k].0];
+                                        return Err(SolverError::contradiction(r, c, Some(d))
+                                            .in_unit(Some(unit), subset));
                                     }
                                     _ => {}
                                 }
@@ -1,59 +1,10 @@
-use crate::SolverError;
-use crate::board::Board;
-use crate::strategy::{Strategy, StrategyKind};
+//! Former name of the pointing check, now covering pointing triples as well.
 
-pub struct PointingPair;
+use super::locked_candidates::LockedCandidatesType1;
 
-impl Strategy for PointingPair {
-    fn kind(&self) -> StrategyKind {
-        StrategyKind::PointingPair
-    }
+#[deprecated(note = "use LockedCandidatesType1")]
+pub type PointingPair = LockedCandidatesType1;
 
-    fn apply(&self, board: &mut Board) -> Result<bool, SolverError> {
-        board.try_for_each_box_digit_mut(|b, unit, digit| {
-            let mut positions = Vec::new();
-            b.for_each_in_unit(unit, |r, c, val| {
-                if val.is_none() && b.candidates(r, c).contains(digit) {
-                    positions.push((r, c));
-                }
-            });
-            if positions.len() == 2 {
-                let same_row = positions[0].0 == positions[1].0;
-                let same_col = positions[0].1 == positions[1].1;
-                if same_row {
-                    let row = positions[0].0;
-                    let mut changed = false;
-                    for c in 0..9 {
-                        if !unit.contains(row, c) {
-                            match b.eliminate_candidate(row, c, digit) {
-                                Some(true) => changed = true,
-                                None => return Err(SolverError::Contradiction { row, col: c }),
-                                _ => {}
-                            }
-                        }
-                    }
-                    if changed {
-                        return Ok(true);
-                    }
-                }
-                if same_col {
-                    let col = positions[0].1;
-                    let mut changed = false;
-                    for r in 0..9 {
-                        if !unit.contains(r, col) {
-                            match b.eliminate_candidate(r, col, digit) {
-                                Some(true) => changed = true,
-                                None => return Err(SolverError::Contradiction { row: r, col }),
-                                _ => {}
-                            }
-                        }
-                    }
-                    if changed {
-                        return Ok(true);
-                    }
-                }
-            }
-            Ok(false)
-        })
-    }
-}
+#[deprecated(note = "use LockedCandidatesType1")]
+#[allow(non_upper_case_globals)]
+pub const PointingPair: LockedCandidatesType1 = LockedCandidatesType1;
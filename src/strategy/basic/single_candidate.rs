@@ -1,6 +1,6 @@
 use crate::SolverError;
 use crate::board::{Board, Digit};
-use crate::strategy::{Strategy, StrategyKind};
+use crate::strategy::{Deduction, Strategy, StrategyKind};
 
 pub struct SingleCandidate;
 
@@ -28,4 +28,18 @@ impl Strategy for SingleCandidate {
             Ok(false)
         }
     }
+
+    fn deductions(&self, board: &Board) -> Option<Vec<Deduction>> {
+        let singles = board
+            .unsolved_cells()
+            .filter_map(|(r, c)| {
+                let cand = board.candidates(r, c);
+                (cand.len() == 1)
+                    .then(|| cand.iter().next())
+                    .flatten()
+                    .map(|d| Deduction::Place(r, c, d))
+            })
+            .collect();
+        Some(singles)
+    }
 }
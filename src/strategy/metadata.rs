@@ -0,0 +1,529 @@
+//! Names, aliases and descriptions of the strategy kinds.
+
+use super::{ALL_KINDS, StrategyKind};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Group of related techniques a strategy belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StrategyFamily {
+    /// A value is the only one left for a cell or a unit.
+    Singles,
+    /// Naked and hidden subsets and almost locked sets.
+    Subsets,
+    /// Digits locked in the intersection of units.
+    Intersections,
+    /// Digits confined to a set of lines.
+    Fish,
+    /// Pivot cells with pincers.
+    Wings,
+    /// Chains and loops of implications.
+    Chains,
+    /// Patterns ruled out by the puzzle having one solution.
+    Uniqueness,
+    /// Trial and error or exhaustive enumeration.
+    Brute,
+}
+
+/// Static description of a strategy kind.
+#[derive(Debug)]
+pub struct StrategyInfo {
+    /// Canonical name, lower case words joined by dashes.
+    pub name: &'static str,
+    /// Other accepted names besides the canonical and the type name.
+    pub aliases: &'static [&'static str],
+    pub description: &'static str,
+    pub family: StrategyFamily,
+    /// Whether the strategy is only sound for puzzles with a single solution.
+    pub assumes_uniqueness: bool,
+}
+
+use StrategyFamily::*;
+
+const fn info(
+    name: &'static str,
+    aliases: &'static [&'static str],
+    family: StrategyFamily,
+    description: &'static str,
+) -> StrategyInfo {
+    StrategyInfo {
+        name,
+        aliases,
+        description,
+        family,
+        assumes_uniqueness: matches!(family, StrategyFamily::Uniqueness),
+    }
+}
+
+/// Metadata of every kind.
+static INFOS: [(StrategyKind, StrategyInfo); 43] = [
+    (
+        StrategyKind::SingleCandidate,
+        info(
+            "single-candidate",
+            &["naked-single"],
+            Singles,
+            "A cell has only one candidate left.",
+        ),
+    ),
+    (
+        StrategyKind::HiddenSingle,
+        info(
+            "hidden-single",
+            &[],
+            Singles,
+            "A digit has only one position left in a unit.",
+        ),
+    ),
+    (
+        StrategyKind::NakedPair,
+        info(
+            "naked-pair",
+            &[],
+            Subsets,
+            "Two cells of a unit hold the same two candidates.",
+        ),
+    ),
+    (
+        StrategyKind::NakedTriple,
+        info(
+            "naked-triple",
+            &[],
+            Subsets,
+            "Three cells of a unit hold only three candidates between them.",
+        ),
+    ),
+    (
+        StrategyKind::NakedQuad,
+        info(
+            "naked-quad",
+            &[],
+            Subsets,
+            "Four cells of a unit hold only four candidates between them.",
+        ),
+    ),
+    (
+        StrategyKind::HiddenPair,
+        info(
+            "hidden-pair",
+            &[],
+            Subsets,
+            "Two digits of a unit fit only in the same two cells.",
+        ),
+    ),
+    (
+        StrategyKind::HiddenTriple,
+        info(
+            "hidden-triple",
+            &[],
+            Subsets,
+            "Three digits of a unit fit only in the same three cells.",
+        ),
+    ),
+    (
+        StrategyKind::HiddenQuad,
+        info(
+            "hidden-quad",
+            &[],
+            Subsets,
+            "Four digits of a unit fit only in the same four cells.",
+        ),
+    ),
+    (
+        StrategyKind::LockedCandidatesType1,
+        info(
+            "locked-candidates-type-1",
+            // box-line-reduction was the name of this check before the two
+            // directions were split, see `box_line_reduction::BoxLineReduction`
+            &[
+                "pointing",
+                "pointing-pair",
+                "pointing-triple",
+                "box-line-reduction",
+            ],
+            Intersections,
+            "A digit of a box lies in one line, so it leaves the rest of the line.",
+        ),
+    ),
+    (
+        StrategyKind::LockedCandidatesType2,
+        info(
+            "locked-candidates-type-2",
+            &["claiming"],
+            Intersections,
+            "A digit of a line lies in one box, so it leaves the rest of the box.",
+        ),
+    ),
+    (
+        StrategyKind::XWing,
+        info(
+            "x-wing",
+            &[],
+            Fish,
+            "A digit of two lines lies in the same two cross lines.",
+        ),
+    ),
+    (
+        StrategyKind::YWing,
+        info(
+            "y-wing",
+            &[],
+            Wings,
+            "A bivalue pivot and two bivalue pincers share a digit.",
+        ),
+    ),
+    (
+        StrategyKind::XYZWing,
+        info(
+            "xyz-wing",
+            &[],
+            Wings,
+            "A trivalue pivot and two bivalue pincers share a digit.",
+        ),
+    ),
+    (
+        StrategyKind::XYWing,
+        info(
+            "xy-wing",
+            &[],
+            Wings,
+            "Three bivalue cells of which the pincers share a digit.",
+        ),
+    ),
+    (
+        StrategyKind::SueDeCoq,
+        info(
+            "sue-de-coq",
+            &["two-sector-disjoint-subsets"],
+            Intersections,
+            "Cells of a box and line intersection split their digits between both units.",
+        ),
+    ),
+    (
+        StrategyKind::AlignedPairExclusion,
+        info(
+            "aligned-pair-exclusion",
+            &["ape"],
+            Subsets,
+            "Combinations of two cells are ruled out by almost locked sets.",
+        ),
+    ),
+    (
+        StrategyKind::AlignedTripleExclusion,
+        info(
+            "aligned-triple-exclusion",
+            &["ate"],
+            Subsets,
+            "Combinations of three cells are ruled out by almost locked sets.",
+        ),
+    ),
+    (
+        StrategyKind::XYChain,
+        info(
+            "xy-chain",
+            &[],
+            Chains,
+            "A chain of bivalue cells whose ends share a digit.",
+        ),
+    ),
+    (
+        StrategyKind::SimpleColoring,
+        info(
+            "simple-coloring",
+            &["single-chains"],
+            Chains,
+            "Two colors along the conjugate pairs of one digit.",
+        ),
+    ),
+    (
+        StrategyKind::MultiColoring,
+        info(
+            "multi-coloring",
+            &[],
+            Chains,
+            "Several color clusters of one digit seeing each other.",
+        ),
+    ),
+    (
+        StrategyKind::ThreeDMedusa,
+        info(
+            "3d-medusa",
+            &["medusa"],
+            Chains,
+            "Coloring across digits through conjugate pairs and bivalue cells.",
+        ),
+    ),
+    (
+        StrategyKind::Jellyfish,
+        info(
+            "jellyfish",
+            &[],
+            Fish,
+            "A digit of four lines lies in the same four cross lines.",
+        ),
+    ),
+    (
+        StrategyKind::UniqueRectangle,
+        info(
+            "unique-rectangle",
+            &["ur", "unique-rectangle-type-1"],
+            Uniqueness,
+            "Three corners of a deadly rectangle rule its pair out of the fourth.",
+        ),
+    ),
+    (
+        StrategyKind::UniqueRectangleType2,
+        info(
+            "unique-rectangle-type-2",
+            &["ur2"],
+            Uniqueness,
+            "Two corners of a deadly rectangle share one extra digit.",
+        ),
+    ),
+    (
+        StrategyKind::UniqueRectangleType3,
+        info(
+            "unique-rectangle-type-3",
+            &["ur3"],
+            Uniqueness,
+            "The extra digits of a deadly rectangle form a subset with other cells.",
+        ),
+    ),
+    (
+        StrategyKind::UniqueRectangleType4,
+        info(
+            "unique-rectangle-type-4",
+            &["ur4"],
+            Uniqueness,
+            "A rectangle digit is locked in two corners, ruling out the other.",
+        ),
+    ),
+    (
+        StrategyKind::UniqueRectangleType5,
+        info(
+            "unique-rectangle-type-5",
+            &["ur5"],
+            Uniqueness,
+            "Diagonal corners of a deadly rectangle share one extra digit.",
+        ),
+    ),
+    (
+        StrategyKind::UniqueRectangleType6,
+        info(
+            "unique-rectangle-type-6",
+            &["ur6"],
+            Uniqueness,
+            "An X-Wing on the rectangle digit across diagonal corners.",
+        ),
+    ),
+    (
+        StrategyKind::HiddenRectangle,
+        info(
+            "hidden-rectangle",
+            &[],
+            Uniqueness,
+            "Strong links around a rectangle avoid the deadly pattern.",
+        ),
+    ),
+    (
+        StrategyKind::AvoidableRectangle,
+        info(
+            "avoidable-rectangle",
+            &[],
+            Uniqueness,
+            "Solved non-given corners avoid a deadly rectangle.",
+        ),
+    ),
+    (
+        StrategyKind::Swordfish,
+        info(
+            "swordfish",
+            &[],
+            Fish,
+            "A digit of three lines lies in the same three cross lines.",
+        ),
+    ),
+    (
+        StrategyKind::Bug,
+        info(
+            "bug",
+            // bug+1 is the legacy name from when only one extra cell was
+            // handled
+            &["bug+n", "bivalue-universal-grave", "bug+1"],
+            Uniqueness,
+            "Cells outside a bivalue universal grave must break it.",
+        ),
+    ),
+    (
+        StrategyKind::DeathBlossom,
+        info(
+            "death-blossom",
+            &[],
+            Chains,
+            "Almost locked sets hanging off each candidate of a stem cell.",
+        ),
+    ),
+    (
+        StrategyKind::JuniorExocet,
+        info(
+            "junior-exocet",
+            &["je"],
+            Intersections,
+            "Base digits covered by two lines are repeated in two target cells.",
+        ),
+    ),
+    (
+        StrategyKind::Exocet,
+        info(
+            "exocet",
+            &[],
+            Intersections,
+            "Base digits covered by two houses are repeated in two target cells.",
+        ),
+    ),
+    (
+        StrategyKind::SkLoop,
+        info(
+            "sk-loop",
+            &["domino-loop"],
+            Intersections,
+            "Eight segments around four boxes linked into a loop.",
+        ),
+    ),
+    (
+        StrategyKind::MultiSectorLockedSet,
+        info(
+            "multi-sector-locked-set",
+            &["msls"],
+            Intersections,
+            "Rows and columns whose intersection needs all their placements.",
+        ),
+    ),
+    (
+        StrategyKind::Nishio,
+        info(
+            "nishio",
+            &[],
+            Brute,
+            "Placing a digit leaves another unit without a position for it.",
+        ),
+    ),
+    (
+        StrategyKind::PatternOverlay,
+        info(
+            "pattern-overlay",
+            &["templates"],
+            Brute,
+            "Candidates used by no valid placement pattern of their digit.",
+        ),
+    ),
+    (
+        StrategyKind::KrakenFish,
+        info(
+            "kraken-fish",
+            &[],
+            Fish,
+            "A finned fish whose fins all lead to the same elimination.",
+        ),
+    ),
+    (
+        StrategyKind::CellForcingChain,
+        info(
+            "cell-forcing-chain",
+            &[],
+            Chains,
+            "Every candidate of a cell leads to the same conclusion.",
+        ),
+    ),
+    (
+        StrategyKind::UnitForcingChain,
+        info(
+            "unit-forcing-chain",
+            &[],
+            Chains,
+            "Every position of a digit in a unit leads to the same conclusion.",
+        ),
+    ),
+    (
+        StrategyKind::DynamicForcingChain,
+        info(
+            "dynamic-forcing-chain",
+            &[],
+            Chains,
+            "A candidate both true and false leads to the same conclusion.",
+        ),
+    ),
+];
+
+impl StrategyKind {
+    pub fn info(self) -> &'static StrategyInfo {
+        INFOS
+            .iter()
+            .find(|(kind, _)| *kind == self)
+            .map(|(_, info)| info)
+            .expect("every kind has metadata")
+    }
+
+    /// Canonical name, such as `x-wing`.
+    pub fn name(self) -> &'static str {
+        self.info().name
+    }
+
+    pub fn description(self) -> &'static str {
+        self.info().description
+    }
+
+    pub fn family(self) -> StrategyFamily {
+        self.info().family
+    }
+
+    pub fn assumes_uniqueness(self) -> bool {
+        self.info().assumes_uniqueness
+    }
+}
+
+impl fmt::Display for StrategyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Error returned when a name matches no strategy kind.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseStrategyKindError(pub String);
+
+impl fmt::Display for ParseStrategyKindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown strategy `{}`", self.0)
+    }
+}
+
+impl Error for ParseStrategyKindError {}
+
+/// Parses the canonical name, an alias or the type name, ignoring case,
+/// dashes, underscores and spaces: `x-wing`, `XWing` and `X_Wing` all name
+/// the X-Wing.
+impl FromStr for StrategyKind {
+    type Err = ParseStrategyKindError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let wanted = normalize(s);
+        ALL_KINDS
+            .into_iter()
+            .find(|kind| {
+                let info = kind.info();
+                normalize(info.name) == wanted
+                    || normalize(&format!("{:?}", kind)) == wanted
+                    || info.aliases.iter().any(|alias| normalize(alias) == wanted)
+            })
+            .ok_or_else(|| ParseStrategyKindError(s.to_string()))
+    }
+}
+
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, '-' | '_' | ' '))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
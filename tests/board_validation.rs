@@ -0,0 +1,93 @@
+use sudoku_evaluator::board::{Board, Duplicate, Unit};
+
+#[test]
+fn validate_accepts_puzzle() {
+    let puzzle =
+        "530070000600195000098000060800060003400803001700020006060000280000419005000080079";
+    let report = Board::parse(puzzle).unwrap().validate();
+    assert!(report.is_ok());
+    assert_eq!(report.to_string(), "");
+}
+
+#[test]
+fn validate_lists_every_duplicate() {
+    let puzzle = format!("11{}1{}", ".".repeat(7), ".".repeat(71));
+    let board = Board::parse(&puzzle).unwrap();
+    let report = board.validate();
+    assert!(!report.is_ok());
+    assert_eq!(
+        report.duplicates,
+        vec![
+            Duplicate {
+                unit: Unit::Row(0),
+                digit: 1,
+                cells: vec![(0, 0), (0, 1)],
+            },
+            Duplicate {
+                unit: Unit::Col(0),
+                digit: 1,
+                cells: vec![(0, 0), (1, 0)],
+            },
+            Duplicate {
+                unit: Unit::Box(0, 0),
+                digit: 1,
+                cells: vec![(0, 0), (0, 1), (1, 0)],
+            },
+        ]
+    );
+    assert_eq!(report.duplicates, board.duplicates());
+    assert!(
+        report
+            .to_string()
+            .starts_with("row 0 holds 1 more than once, at (0, 0), (0, 1)\n")
+    );
+}
+
+#[test]
+fn validate_lists_empty_cells_and_missing_digits() {
+    // row 0 holds 1 to 8, and 9 is removed from its last cell
+    let puzzle = format!("12345678.{}", ".".repeat(72));
+    let mut board = Board::parse(&puzzle).unwrap();
+    assert!(board.validate().is_ok());
+    board.eliminate_candidate(0, 8, 9);
+    let report = board.validate();
+    assert!(report.duplicates.is_empty());
+    assert_eq!(report.empty_cells, vec![(0, 8)]);
+    assert_eq!(report.missing_digits, vec![(Unit::Row(0), 9)]);
+    assert!(report.forced_twice.is_empty());
+    assert!(report.removed_placed.is_empty());
+    assert!(
+        report
+            .to_string()
+            .contains("(0, 8) has no candidates left\nrow 0 has no place left for 9\n")
+    );
+}
+
+#[test]
+fn validate_lists_cells_forced_to_the_same_digit() {
+    // removing 2 to 9 from the first two cells of row 0 leaves both with 1
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    for c in 0..2 {
+        for d in 2..=9 {
+            board.eliminate_candidate(0, c, d);
+        }
+    }
+    let report = board.validate();
+    assert!(report.duplicates.is_empty());
+    assert!(report.empty_cells.is_empty());
+    assert!(report.missing_digits.is_empty());
+    let forced = |unit| Duplicate {
+        unit,
+        digit: 1,
+        cells: vec![(0, 0), (0, 1)],
+    };
+    assert_eq!(
+        report.forced_twice,
+        vec![forced(Unit::Row(0)), forced(Unit::Box(0, 0))]
+    );
+    assert!(
+        report
+            .to_string()
+            .starts_with("row 0 leaves 1 as the only candidate of (0, 0), (0, 1)\n")
+    );
+}
@@ -12,3 +12,95 @@ fn solve_cli_succeeds() {
     let stdout = String::from_utf8(output.stdout).unwrap();
     assert!(stdout.contains("Solved with strategies"));
 }
+
+#[test]
+fn solve_cli_uses_profile() {
+    let puzzle =
+        "530070000600195000098000060800060003400803001700020006060000280000419005000080079";
+    let path = std::env::temp_dir().join(format!("singles-{}.toml", std::process::id()));
+    std::fs::write(&path, r#"strategies = ["SingleCandidate"]"#).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_solve"))
+        .args(["--profile", path.to_str().unwrap(), puzzle])
+        .output()
+        .expect("failed to run solve binary");
+    std::fs::remove_file(&path).unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Solved with strategies: single-candidate\n"));
+}
+
+#[test]
+fn solve_cli_only_named_strategies() {
+    let puzzle =
+        "530070000600195000098000060800060003400803001700020006060000280000419005000080079";
+    let output = Command::new(env!("CARGO_BIN_EXE_solve"))
+        .args(["--only", "x-wing,hidden-single", puzzle])
+        .output()
+        .expect("failed to run solve binary");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Solved with strategies: hidden-single\n"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_solve"))
+        .args(["--only", "guess", puzzle])
+        .output()
+        .expect("failed to run solve binary");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn solve_cli_stops_at_max_steps() {
+    let puzzle =
+        "530070000600195000098000060800060003400803001700020006060000280000419005000080079";
+    let output = Command::new(env!("CARGO_BIN_EXE_solve"))
+        .args(["--max-steps", "2", puzzle])
+        .output()
+        .expect("failed to run solve binary");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("solve budget exceeded after 2 steps"));
+}
+
+#[test]
+fn solve_cli_traces_steps() {
+    let puzzle =
+        "530070000600195000098000060800060003400803001700020006060000280000419005000080079";
+    let output = Command::new(env!("CARGO_BIN_EXE_solve"))
+        .args(["--trace", puzzle])
+        .output()
+        .expect("failed to run solve binary");
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.lines().count() > 1);
+    assert!(
+        stderr
+            .lines()
+            .all(|line| line.starts_with("single-candidate ("))
+    );
+}
+
+#[test]
+fn solve_cli_rejects_unknown_options() {
+    let puzzle =
+        "530070000600195000098000060800060003400803001700020006060000280000419005000080079";
+    let output = Command::new(env!("CARGO_BIN_EXE_solve"))
+        .args(["--max-step", "2", puzzle])
+        .output()
+        .expect("failed to run solve binary");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("usage: "));
+    assert!(stderr.contains("unknown option --max-step"));
+}
+
+#[test]
+fn evaluator_cli_keeps_singles_with_only() {
+    let puzzle =
+        "530070000600195000098000060800060003400803001700020006060000280000419005000080079";
+    let output = Command::new(env!("CARGO_BIN_EXE_evaluator"))
+        .args(["--only", "x-wing", puzzle])
+        .output()
+        .expect("failed to run evaluator binary");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Solved with strategies: single-candidate, hidden-single\n"));
+}
@@ -0,0 +1,123 @@
+use sudoku_evaluator::{
+    Profile, ProfileError, Solver, SolverBuilder, board::Board, strategy::StrategyKind,
+};
+
+const PUZZLE: &str =
+    "530070000600195000098000060800060003400803001700020006060000280000419005000080079";
+
+#[test]
+fn toml_profile_orders_strategies() {
+    let profile = Profile::from_toml(
+        r#"
+        strategies = [
+            "HiddenSingle",
+            { name = "XYChain", max_length = 6 },
+            { name = "Nishio", enabled = false },
+            { name = "KrakenFish", max_size = 3, depth = "singles" },
+            "SingleCandidate",
+        ]
+        "#,
+    )
+    .unwrap();
+    let solver = SolverBuilder::from_profile(&profile).unwrap().build();
+    assert_eq!(
+        solver.kinds(),
+        vec![
+            StrategyKind::HiddenSingle,
+            StrategyKind::XYChain,
+            StrategyKind::KrakenFish,
+            StrategyKind::SingleCandidate,
+        ]
+    );
+    let mut board = Board::parse(PUZZLE).unwrap();
+    assert!(solver.solve(&mut board).is_ok());
+}
+
+#[test]
+fn json_profile_matches_toml() {
+    let json = Profile::from_json(
+        r#"{"strategies": ["SingleCandidate", {"name": "PatternOverlay", "combine_pairs": true}]}"#,
+    )
+    .unwrap();
+    let toml = Profile::from_toml(
+        r#"strategies = ["SingleCandidate", { name = "PatternOverlay", combine_pairs = true }]"#,
+    )
+    .unwrap();
+    assert_eq!(json, toml);
+}
+
+#[test]
+fn profile_rejects_bad_entries() {
+    let error = |text: &str| {
+        SolverBuilder::from_profile(&Profile::from_toml(text).unwrap())
+            .err()
+            .unwrap()
+    };
+    assert!(matches!(
+        error(r#"strategies = ["Guessing"]"#),
+        ProfileError::UnknownStrategy(name) if name == "Guessing"
+    ));
+    assert!(matches!(
+        error(r#"strategies = [{ name = "XWing", depth = "full" }]"#),
+        ProfileError::UnsupportedParameter {
+            strategy: StrategyKind::XWing,
+            parameter: "depth"
+        }
+    ));
+    assert!(matches!(
+        error(r#"strategies = [{ name = "KrakenFish", max_size = 5 }]"#),
+        ProfileError::InvalidParameter {
+            strategy: StrategyKind::KrakenFish,
+            parameter: "max_size",
+            ..
+        }
+    ));
+    assert!(matches!(
+        Profile::from_toml(r#"ladder = ["XWing"]"#),
+        Err(ProfileError::Parse(_))
+    ));
+}
+
+#[test]
+fn builder_removes_kinds() {
+    let solver = Solver::builder()
+        .kinds([
+            StrategyKind::SingleCandidate,
+            StrategyKind::HiddenSingle,
+            StrategyKind::Nishio,
+        ])
+        .without(StrategyKind::Nishio)
+        .build();
+    assert_eq!(
+        solver.kinds(),
+        vec![StrategyKind::SingleCandidate, StrategyKind::HiddenSingle]
+    );
+    assert!(
+        !Solver::without_nishio_and_forcing_chain()
+            .kinds()
+            .contains(&StrategyKind::CellForcingChain)
+    );
+}
+
+#[test]
+fn profile_sets_policy() {
+    use sudoku_evaluator::SolvePolicy;
+    let profile = Profile::from_toml(
+        r#"
+        policy = "round-robin"
+        strategies = ["single-candidate", "hidden-single"]
+        "#,
+    )
+    .unwrap();
+    let solver = SolverBuilder::from_profile(&profile).unwrap().build();
+    assert_eq!(solver.policy(), SolvePolicy::RoundRobin);
+    let profile = Profile::from_toml(
+        r#"policy = "fastest"
+strategies = []"#,
+    )
+    .unwrap();
+    assert!(matches!(
+        SolverBuilder::from_profile(&profile),
+        Err(ProfileError::Parse(_))
+    ));
+}
@@ -21,5 +21,135 @@ fn progressive_solver_unsolvable() {
     let mut board = Board::parse(puzzle).unwrap();
     let solver = ProgressiveSolver::default();
     let err = solver.solve(&mut board).unwrap_err();
-    assert!(matches!(err, SolverError::Unsolvable));
+    // the puzzle has no solution, and pattern overlay runs out of templates
+    assert!(
+        matches!(
+            err,
+            SolverError::Contradiction {
+                strategy: Some(StrategyKind::PatternOverlay),
+                ..
+            }
+        ),
+        "{:?}",
+        err
+    );
+}
+
+#[test]
+fn progressive_solver_reports_bottlenecks() {
+    use sudoku_evaluator::strategy::kind_to_strategy;
+    let puzzle =
+        "000003017015009008060000000100007000009000200000500004000000020500600340340200000";
+    let solver = ProgressiveSolver::default();
+    let mut solved = Board::parse(puzzle).unwrap();
+    let kinds = solver.solve(&mut solved).unwrap();
+    let mut board = Board::parse(puzzle).unwrap();
+    let bottlenecks = solver.bottlenecks(&mut board).unwrap();
+    assert_eq!(board.to_string(), solved.to_string());
+    assert!(!bottlenecks.is_empty());
+    let simplest: Vec<_> = bottlenecks.iter().map(|b| b.simplest).collect();
+    assert_eq!(simplest, kinds[2..]);
+    for bottleneck in &bottlenecks {
+        assert!(!bottleneck.board.is_solved());
+        assert_eq!(bottleneck.working.first(), Some(&bottleneck.simplest));
+        for &kind in &bottleneck.working {
+            let mut probe = bottleneck.board.clone();
+            assert!(kind_to_strategy(kind).apply(&mut probe).unwrap());
+        }
+    }
+    assert!(bottlenecks.windows(2).all(|w| w[0].step <= w[1].step));
+}
+
+#[test]
+fn progressive_solver_prunes_unneeded_strategies() {
+    use sudoku_evaluator::Solver;
+    let puzzle =
+        "6.....8.3.4.7.................5.4.7.3..2.....1.6.......2.....5.....8.6......1....";
+    let mut solved = Board::parse(puzzle).unwrap();
+    let found = ProgressiveSolver::default().solve(&mut solved).unwrap();
+    let mut board = Board::parse(puzzle).unwrap();
+    let pruned = ProgressiveSolver::default()
+        .with_pruning()
+        .solve(&mut board)
+        .unwrap();
+    assert_eq!(board.to_string(), solved.to_string());
+    assert!(pruned.len() < found.len());
+    assert!(pruned.iter().all(|kind| found.contains(kind)));
+    let mut check = Board::parse(puzzle).unwrap();
+    Solver::builder()
+        .kinds(pruned.iter().copied())
+        .build()
+        .solve(&mut check)
+        .unwrap();
+    for &kind in &pruned[2..] {
+        let mut without = Board::parse(puzzle).unwrap();
+        let rest = pruned.iter().copied().filter(|&k| k != kind);
+        assert!(
+            Solver::builder()
+                .kinds(rest)
+                .build()
+                .solve(&mut without)
+                .is_err()
+        );
+    }
+}
+
+#[test]
+fn progressive_solver_respects_budget() {
+    use sudoku_evaluator::Budget;
+    let puzzle =
+        "000003017015009008060000000100007000009000200000500004000000020500600340340200000";
+    let mut board = Board::parse(puzzle).unwrap();
+    let solver = ProgressiveSolver::default().with_budget(Budget::default().with_max_steps(5));
+    match solver.solve(&mut board) {
+        Err(SolverError::BudgetExceeded { steps, .. }) => assert_eq!(steps, 5),
+        other => panic!("expected budget exceeded, got {:?}", other),
+    }
+}
+
+#[test]
+fn progressive_solver_can_be_cancelled() {
+    use sudoku_evaluator::CancelToken;
+    let puzzle =
+        "000003017015009008060000000100007000009000200000500004000000020500600340340200000";
+    let mut board = Board::parse(puzzle).unwrap();
+    let token = CancelToken::new();
+    token.cancel();
+    let err = ProgressiveSolver::default()
+        .with_cancel_token(token)
+        .solve(&mut board)
+        .unwrap_err();
+    assert!(matches!(err, SolverError::Cancelled { .. }));
+}
+
+#[test]
+fn pruning_trials_are_not_observed() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::time::Duration;
+    use sudoku_evaluator::{SolveObserver, SolvePolicy, Solver};
+
+    #[derive(Default)]
+    struct Steps(Cell<usize>);
+
+    impl SolveObserver for Steps {
+        fn after_apply(&self, _kind: StrategyKind, changed: bool, _elapsed: Duration) {
+            if changed {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+    }
+
+    let puzzle =
+        "6.....8.3.4.7.................5.4.7.3..2.....1.6.......2.....5.....8.6......1....";
+    let mut board = Board::parse(puzzle).unwrap();
+    let report = Solver::default()
+        .with_policy(SolvePolicy::MinimalLadder)
+        .solve_with_report(&mut board)
+        .unwrap();
+    let steps = Rc::new(Steps::default());
+    let solver = ProgressiveSolver::from(Solver::default().with_observer(steps.clone()));
+    let mut board = Board::parse(puzzle).unwrap();
+    solver.with_pruning().solve(&mut board).unwrap();
+    assert_eq!(steps.0.get(), report.steps);
 }
@@ -1,6 +1,6 @@
 use sudoku_evaluator::{
     Solver,
-    board::Board,
+    board::{Board, Unit},
     strategy::{Strategy, StrategyKind},
 };
 
@@ -31,7 +31,139 @@ fn invalid_board() {
     let mut board = Board::parse(&puzzle).unwrap();
     let solver = Solver::default();
     let err = solver.solve(&mut board).unwrap_err();
-    assert!(matches!(err, sudoku_evaluator::SolverError::InvalidBoard));
+    match err {
+        sudoku_evaluator::SolverError::InvalidBoard { duplicates } => {
+            // the two ones share both row 0 and the top left box
+            let units: Vec<_> = duplicates.iter().map(|d| d.unit).collect();
+            assert_eq!(units, vec![Unit::Row(0), Unit::Box(0, 0)]);
+            assert!(duplicates.iter().all(|d| d.digit == 1));
+            assert!(duplicates.iter().all(|d| d.cells == vec![(0, 0), (0, 1)]));
+        }
+        other => panic!("expected invalid board, got {:?}", other),
+    }
+    assert_eq!(
+        err_message(&puzzle),
+        "board is invalid: row 0 holds 1 more than once, at (0, 0), (0, 1); \
+         box at (0, 0) holds 1 more than once, at (0, 0), (0, 1)"
+    );
+}
+
+/// Remove every candidate of the cell but `digits`.
+fn keep_candidates(board: &mut Board, r: usize, c: usize, digits: &[u8]) {
+    for d in (1..=9).filter(|d| !digits.contains(d)) {
+        board.eliminate_candidate(r, c, d);
+    }
+}
+
+fn err_message(puzzle: &str) -> String {
+    let mut board = Board::parse(puzzle).unwrap();
+    Solver::default().solve(&mut board).unwrap_err().to_string()
+}
+
+#[test]
+fn contradiction_carries_context() {
+    use sudoku_evaluator::SolverError;
+    use sudoku_evaluator::strategy::naked_pair::NakedPair;
+    // a naked pair of 1 and 2 in row 0 next to a cell left with only 1
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    for c in 0..3 {
+        for d in 3..=9 {
+            board.eliminate_candidate(0, c, d);
+        }
+    }
+    board.eliminate_candidate(0, 2, 2);
+    let start = board.to_string();
+    let err = Solver::new(vec![Box::new(NakedPair)])
+        .reduce(&mut board)
+        .unwrap_err();
+    match &err {
+        SolverError::Contradiction {
+            row,
+            col,
+            digit,
+            unit,
+            cells,
+            strategy,
+            board: Some(snapshot),
+        } => {
+            assert_eq!((*row, *col), (0, 2));
+            assert_eq!(*digit, Some(1));
+            assert_eq!(*unit, Some(Unit::Row(0)));
+            assert_eq!(*cells, vec![(0, 0), (0, 1)]);
+            assert_eq!(*strategy, Some(StrategyKind::NakedPair));
+            assert_eq!(snapshot.to_string(), start);
+        }
+        other => panic!("expected contradiction, got {:?}", other),
+    }
+    assert!(
+        err.to_string()
+            .ends_with("on digit 1 in row 0 found by naked-pair")
+    );
+}
+
+#[test]
+fn fish_contradiction_names_cover_line() {
+    use sudoku_evaluator::SolverError;
+    use sudoku_evaluator::strategy::swordfish::Swordfish;
+    // 1 is confined to columns 0, 4 and 8 in rows 0, 4 and 8, next to a cell
+    // of column 0 left with only 1
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    for r in [0, 4, 8] {
+        for c in (0..9).filter(|c| ![0, 4, 8].contains(c)) {
+            board.eliminate_candidate(r, c, 1);
+        }
+    }
+    for d in 2..=9 {
+        board.eliminate_candidate(3, 0, d);
+    }
+    match Swordfish.apply(&mut board).unwrap_err() {
+        SolverError::Contradiction {
+            row,
+            col,
+            digit,
+            unit,
+            cells,
+            ..
+        } => {
+            assert_eq!((row, col, digit), (3, 0, Some(1)));
+            assert_eq!(unit, Some(Unit::Col(0)));
+            assert_eq!(cells, vec![(0, 0), (4, 0), (8, 0)]);
+        }
+        other => panic!("expected contradiction, got {:?}", other),
+    }
+}
+
+#[test]
+fn coloring_contradiction_names_same_colored_cells() {
+    use sudoku_evaluator::SolverError;
+    use sudoku_evaluator::strategy::simple_coloring::SimpleColoring;
+    // 1 is a conjugate pair in row 0 and in column 1, so (0, 0) and (1, 1)
+    // share a color and see each other in box 0, and (0, 0) holds only 1
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    for c in 2..9 {
+        board.eliminate_candidate(0, c, 1);
+    }
+    for r in 2..9 {
+        board.eliminate_candidate(r, 1, 1);
+    }
+    for d in 2..=9 {
+        board.eliminate_candidate(0, 0, d);
+    }
+    match SimpleColoring.apply(&mut board).unwrap_err() {
+        SolverError::Contradiction {
+            row,
+            col,
+            digit,
+            unit,
+            cells,
+            ..
+        } => {
+            assert_eq!((row, col, digit), (0, 0, Some(1)));
+            assert_eq!(unit, Some(Unit::Box(0, 0)));
+            assert_eq!(cells, vec![(0, 0), (1, 1)]);
+        }
+        other => panic!("expected contradiction, got {:?}", other),
+    }
 }
 
 #[test]
@@ -63,7 +195,12 @@ fn solve_new_puzzle() {
     let mut board = Board::parse(puzzle).unwrap();
     let solver = Solver::default();
     let err = solver.solve(&mut board).unwrap_err();
-    assert!(matches!(err, sudoku_evaluator::SolverError::Unsolvable));
+    // the puzzle has no solution
+    assert!(
+        matches!(err, sudoku_evaluator::SolverError::Contradiction { .. }),
+        "{:?}",
+        err
+    );
 }
 
 #[test]
@@ -94,6 +231,7 @@ fn naked_pair_strategy() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn box_line_reduction_strategy() {
     let mut board = Board::parse(&".".repeat(81)).unwrap();
     // in box (0,0), digit 1 only appears in row 0
@@ -169,6 +307,21 @@ fn y_wing_strategy() {
     assert!(!board.candidates(1, 1).contains(3));
 }
 
+#[test]
+fn xy_chain_starts_from_the_other_pivot_digit() {
+    // (0, 0) {1, 2} -1- (0, 1) {1, 3} -3- (1, 0) {3, 1} only shows that the
+    // pivot is not 1, which says nothing about 1 elsewhere in column 0. The
+    // chain from (0, 1) to (1, 0) still clears 1 from the rest of the box.
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    keep_candidates(&mut board, 0, 0, &[1, 2]);
+    keep_candidates(&mut board, 0, 1, &[1, 3]);
+    keep_candidates(&mut board, 1, 0, &[1, 3]);
+    let strat = sudoku_evaluator::strategy::xy_chain::XYChain::new();
+    assert!(strat.apply(&mut board).unwrap());
+    assert!(!board.candidates(0, 0).contains(1));
+    assert!(board.candidates(5, 0).contains(1));
+}
+
 #[test]
 fn hidden_pair_strategy() {
     let mut board = Board::parse(&".".repeat(81)).unwrap();
@@ -239,6 +392,22 @@ fn hidden_triple_strategy() {
 }
 
 #[test]
+fn hidden_quad_skips_placed_digits() {
+    // 4 is placed in row 0 and 1 to 3 share four cells, which is no quad
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    board.set(0, 8, 4);
+    for c in 4..8 {
+        for d in 1..=3 {
+            board.eliminate_candidate(0, c, d);
+        }
+    }
+    let strat = sudoku_evaluator::strategy::hidden_quad::HiddenQuad;
+    assert!(!strat.apply(&mut board).unwrap());
+    assert!(board.candidates(0, 0).contains(5));
+}
+
+#[test]
+#[allow(deprecated)]
 fn pointing_pair_strategy() {
     let mut board = Board::parse(&".".repeat(81)).unwrap();
     // digit 1 only in cells (0,0) and (0,1) of box 0
@@ -258,6 +427,42 @@ fn pointing_pair_strategy() {
     }
 }
 
+#[test]
+fn locked_candidates_type1_pointing_triple() {
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    // digit 1 only in column 0 of box 0, in all three cells
+    for r in 0..3 {
+        for c in 1..3 {
+            board.eliminate_candidate(r, c, 1);
+        }
+    }
+    let strat = sudoku_evaluator::strategy::locked_candidates::LockedCandidatesType1;
+    assert_eq!(strat.kind(), StrategyKind::LockedCandidatesType1);
+    assert!(strat.apply(&mut board).unwrap());
+    for r in 3..9 {
+        assert!(!board.candidates(r, 0).contains(1));
+    }
+    assert!(board.candidates(0, 0).contains(1));
+}
+
+#[test]
+fn locked_candidates_type2_claiming() {
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    // digit 1 in row 0 only inside box 0
+    for c in 3..9 {
+        board.eliminate_candidate(0, c, 1);
+    }
+    let strat = sudoku_evaluator::strategy::locked_candidates::LockedCandidatesType2;
+    assert_eq!(strat.kind(), StrategyKind::LockedCandidatesType2);
+    assert!(strat.apply(&mut board).unwrap());
+    for r in 1..3 {
+        for c in 0..3 {
+            assert!(!board.candidates(r, c).contains(1));
+        }
+    }
+    assert!(board.candidates(1, 3).contains(1));
+}
+
 #[test]
 fn swordfish_strategy() {
     let mut board = Board::parse(&".".repeat(81)).unwrap();
@@ -274,3 +479,953 @@ fn swordfish_strategy() {
         }
     }
 }
+
+#[test]
+fn swordfish_skips_lines_with_the_digit_placed() {
+    // rows 0 and 1 keep 1 in three columns, and row 8 already holds it
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    board.set(8, 8, 1);
+    for r in 0..2 {
+        for c in [1, 2, 4, 5, 7, 8] {
+            board.eliminate_candidate(r, c, 1);
+        }
+    }
+    let strat = sudoku_evaluator::strategy::swordfish::Swordfish;
+    assert!(!strat.apply(&mut board).unwrap());
+    assert!(board.candidates(4, 0).contains(1));
+}
+
+#[test]
+fn jellyfish_skips_lines_with_the_digit_placed() {
+    // rows 0 to 2 keep 1 in four columns, and row 8 already holds it
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    board.set(8, 8, 1);
+    for r in 0..3 {
+        for c in [1, 2, 4, 5, 8] {
+            board.eliminate_candidate(r, c, 1);
+        }
+    }
+    let strat = sudoku_evaluator::strategy::jellyfish::Jellyfish;
+    assert!(!strat.apply(&mut board).unwrap());
+    assert!(board.candidates(4, 0).contains(1));
+}
+
+#[test]
+fn three_d_medusa_strategy() {
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    // chain of bivalue cells joined by conjugate pairs:
+    // (0,0) {1,2} -2- (0,4) {2,3} -3- (4,4) {3,1}
+    for d in 3..=9 {
+        board.eliminate_candidate(0, 0, d);
+    }
+    for d in (1..=9).filter(|&d| d != 2 && d != 3) {
+        board.eliminate_candidate(0, 4, d);
+    }
+    for d in (2..=9).filter(|&d| d != 3) {
+        board.eliminate_candidate(4, 4, d);
+    }
+    for c in (1..9).filter(|&c| c != 4) {
+        board.eliminate_candidate(0, c, 2);
+    }
+    for r in (1..9).filter(|&r| r != 4) {
+        board.eliminate_candidate(r, 4, 3);
+    }
+    let strat = sudoku_evaluator::strategy::three_d_medusa::ThreeDMedusa;
+    assert!(strat.apply(&mut board).unwrap());
+    // (4,0) sees 1 in both colors: (0,0) and (4,4)
+    assert!(!board.candidates(4, 0).contains(1));
+}
+
+#[test]
+fn multi_coloring_strategy() {
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    // two clusters for digit 1: (0,0)-(0,8) in row 0 and (4,0)-(4,6) in row 4
+    for c in 1..8 {
+        board.eliminate_candidate(0, c, 1);
+    }
+    for c in (1..9).filter(|&c| c != 6) {
+        board.eliminate_candidate(4, c, 1);
+    }
+    let strat = sudoku_evaluator::strategy::multi_coloring::MultiColoring;
+    assert!(strat.apply(&mut board).unwrap());
+    // (0,0) sees (4,0), so (0,8) or (4,6) holds the 1
+    assert!(!board.candidates(3, 8).contains(1));
+    assert!(!board.candidates(1, 6).contains(1));
+    assert!(board.candidates(1, 7).contains(1));
+}
+
+/// Board with a rectangle on rows 0-1 and columns 0 and 3 whose top corners
+/// hold only {1,2}.
+fn rectangle_floor_board() -> Board {
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    keep_candidates(&mut board, 0, 0, &[1, 2]);
+    keep_candidates(&mut board, 0, 3, &[1, 2]);
+    board
+}
+
+#[test]
+fn unique_rectangle_strategy() {
+    let mut board = rectangle_floor_board();
+    for d in 3..=9 {
+        board.eliminate_candidate(1, 0, d);
+    }
+    let strat = sudoku_evaluator::strategy::unique_rectangle::UniqueRectangle;
+    assert!(strat.apply(&mut board).unwrap());
+    // the roof loses the rectangle pair and keeps its extra candidates
+    let roof = board.candidates(1, 3);
+    assert!(!roof.contains(1) && !roof.contains(2));
+    assert_eq!(roof.len(), 7);
+}
+
+#[test]
+fn unique_rectangle_type2_strategy() {
+    let mut board = rectangle_floor_board();
+    for d in 4..=9 {
+        board.eliminate_candidate(1, 0, d);
+        board.eliminate_candidate(1, 3, d);
+    }
+    let strat = sudoku_evaluator::strategy::unique_rectangle::UniqueRectangleType2;
+    assert!(strat.apply(&mut board).unwrap());
+    // one of the roof corners must be 3
+    assert!(!board.candidates(1, 5).contains(3));
+    assert!(board.candidates(1, 3).contains(3));
+    assert!(board.candidates(2, 1).contains(3));
+}
+
+#[test]
+fn unique_rectangle_type4_strategy() {
+    let mut board = rectangle_floor_board();
+    for c in [1, 2, 4, 5, 6, 7, 8] {
+        board.eliminate_candidate(1, c, 1);
+    }
+    let strat = sudoku_evaluator::strategy::unique_rectangle::UniqueRectangleType4;
+    assert!(strat.apply(&mut board).unwrap());
+    assert!(!board.candidates(1, 0).contains(2));
+    assert!(!board.candidates(1, 3).contains(2));
+}
+
+#[test]
+fn unique_rectangle_type3_strategy() {
+    let mut board = rectangle_floor_board();
+    // roofs {1, 2, 3} and {1, 2, 4} form a naked pair with {3, 4} in row 1
+    for d in 4..=9 {
+        board.eliminate_candidate(1, 0, d);
+    }
+    for d in (3..=9).filter(|&d| d != 4) {
+        board.eliminate_candidate(1, 3, d);
+    }
+    for d in (1..=9).filter(|&d| d != 3 && d != 4) {
+        board.eliminate_candidate(1, 6, d);
+    }
+    let strat = sudoku_evaluator::strategy::unique_rectangle::UniqueRectangleType3;
+    assert!(strat.apply(&mut board).unwrap());
+    for c in [1, 2, 4, 5, 7, 8] {
+        assert!(!board.candidates(1, c).contains(3));
+        assert!(!board.candidates(1, c).contains(4));
+    }
+    assert!(board.candidates(1, 0).contains(3));
+    assert!(board.candidates(1, 3).contains(4));
+}
+
+/// Floors at (0, 0) and (1, 3) holding only 1 and 2, leaving the diagonal
+/// roofs (0, 3) and (1, 0) open.
+fn rectangle_diagonal_board() -> Board {
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    keep_candidates(&mut board, 0, 0, &[1, 2]);
+    keep_candidates(&mut board, 1, 3, &[1, 2]);
+    board
+}
+
+#[test]
+fn unique_rectangle_type5_strategy() {
+    let mut board = rectangle_diagonal_board();
+    for d in 4..=9 {
+        board.eliminate_candidate(0, 3, d);
+        board.eliminate_candidate(1, 0, d);
+    }
+    let strat = sudoku_evaluator::strategy::unique_rectangle::UniqueRectangleType5;
+    assert!(strat.apply(&mut board).unwrap());
+    // one of the diagonal roofs must be 3
+    for (r, c) in [(0, 1), (0, 2), (1, 4), (1, 5)] {
+        assert!(!board.candidates(r, c).contains(3));
+    }
+    assert!(board.candidates(0, 3).contains(3));
+    assert!(board.candidates(1, 0).contains(3));
+    assert!(board.candidates(0, 6).contains(3));
+}
+
+#[test]
+fn unique_rectangle_type6_strategy() {
+    let mut board = rectangle_diagonal_board();
+    // 1 forms an X-Wing on the rectangle rows
+    for r in 0..2 {
+        for c in [1, 2, 4, 5, 6, 7, 8] {
+            board.eliminate_candidate(r, c, 1);
+        }
+    }
+    let strat = sudoku_evaluator::strategy::unique_rectangle::UniqueRectangleType6;
+    assert!(strat.apply(&mut board).unwrap());
+    for (r, c) in [(0, 3), (1, 0)] {
+        assert!(!board.candidates(r, c).contains(1));
+        assert!(board.candidates(r, c).contains(2));
+    }
+}
+
+#[test]
+fn hidden_rectangle_strategy() {
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    for d in 3..=9 {
+        board.eliminate_candidate(0, 0, d);
+    }
+    // digit 1 is locked to the rectangle in row 1 and column 3
+    for c in [1, 2, 4, 5, 6, 7, 8] {
+        board.eliminate_candidate(1, c, 1);
+    }
+    for r in 2..9 {
+        board.eliminate_candidate(r, 3, 1);
+    }
+    let strat = sudoku_evaluator::strategy::unique_rectangle::HiddenRectangle;
+    assert!(strat.apply(&mut board).unwrap());
+    assert!(!board.candidates(1, 3).contains(2));
+    assert!(board.candidates(1, 3).contains(1));
+}
+
+#[test]
+fn avoidable_rectangle_strategy() {
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    board.set(0, 0, 1);
+    board.set(0, 3, 2);
+    board.set(1, 0, 2);
+    let strat = sudoku_evaluator::strategy::unique_rectangle::AvoidableRectangle;
+    assert!(strat.apply(&mut board).unwrap());
+    assert!(!board.candidates(1, 3).contains(1));
+
+    // the same pattern built from givens is not avoidable
+    let mut givens = ".".repeat(81);
+    givens.replace_range(0..1, "1");
+    givens.replace_range(3..4, "2");
+    givens.replace_range(9..10, "2");
+    let mut board = Board::parse(&givens).unwrap();
+    assert!(!strat.apply(&mut board).unwrap());
+}
+
+/// Bivalue universal grave: every cell keeps its digit from a solved grid and
+/// the next digit, so each candidate appears exactly twice in every unit.
+/// `extras` adds a third candidate to some cells.
+fn bug_board(extras: &[(usize, usize, u8)]) -> Board {
+    let solved =
+        "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+    let solved = Board::parse(solved).unwrap();
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    for (r, c) in solved.cells() {
+        let digit = solved.get(r, c).unwrap();
+        let mut keep = vec![digit, digit % 9 + 1];
+        keep.extend(
+            extras
+                .iter()
+                .filter(|&&(er, ec, _)| (er, ec) == (r, c))
+                .map(|&(_, _, d)| d),
+        );
+        keep_candidates(&mut board, r, c, &keep);
+    }
+    board
+}
+
+#[test]
+fn bug_strategy() {
+    let strat = sudoku_evaluator::strategy::bug::Bug;
+
+    // BUG+1: the extra candidate must be placed
+    let mut board = bug_board(&[(0, 0, 7)]);
+    assert!(strat.apply(&mut board).unwrap());
+    assert_eq!(board.get(0, 0), Some(7));
+
+    // BUG+2: one of the two extra 7s is true
+    let mut board = bug_board(&[(0, 0, 7), (0, 1, 7)]);
+    assert!(strat.apply(&mut board).unwrap());
+    assert!(!board.candidates(0, 4).contains(7));
+    assert!(!board.candidates(1, 0).contains(7));
+    assert!(board.candidates(0, 0).contains(7));
+
+    // a bivalue grid without extra candidates gives nothing to deduce
+    let mut board = bug_board(&[]);
+    assert!(!strat.apply(&mut board).unwrap());
+}
+
+#[test]
+fn sue_de_coq_strategy() {
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    // (0,0) and (0,1) hold {1,2,3,4}, (0,5) holds {1,2} and (1,0) holds {3,4}
+    for d in 5..=9 {
+        board.eliminate_candidate(0, 0, d);
+        board.eliminate_candidate(0, 1, d);
+    }
+    for d in 3..=9 {
+        board.eliminate_candidate(0, 5, d);
+    }
+    for d in (1..=9).filter(|&d| d != 3 && d != 4) {
+        board.eliminate_candidate(1, 0, d);
+    }
+    let strat = sudoku_evaluator::strategy::sue_de_coq::SueDeCoq;
+    assert!(strat.apply(&mut board).unwrap());
+    assert_eq!(board.candidates(0, 3), vec![3, 4, 5, 6, 7, 8, 9]);
+    assert_eq!(board.candidates(2, 2), vec![1, 2, 5, 6, 7, 8, 9]);
+    assert_eq!(board.candidates(0, 2), vec![5, 6, 7, 8, 9]);
+}
+
+#[test]
+fn death_blossom_strategy() {
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    // stem (0,0) {1,2}; petal for 1: (0,4) {1,3};
+    // petal for 2: almost locked set (4,0) {2,3,4} and (5,0) {2,4}
+    keep_candidates(&mut board, 0, 0, &[1, 2]);
+    keep_candidates(&mut board, 0, 4, &[1, 3]);
+    keep_candidates(&mut board, 4, 0, &[2, 3, 4]);
+    keep_candidates(&mut board, 5, 0, &[2, 4]);
+    let strat = sudoku_evaluator::strategy::death_blossom::DeathBlossom;
+    assert!(strat.apply(&mut board).unwrap());
+    assert!(!board.candidates(4, 4).contains(3));
+}
+
+/// Base cells (0,0) and (0,1) holding {1,2}, targets (1,3) and (2,6) with
+/// companions (2,3) and (1,6) free of base digits. Outside the band the
+/// cross-lines (columns 2, 3 and 6) keep the base digits in the given rows.
+fn exocet_board(rows_of_one: &[usize]) -> Board {
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    keep_candidates(&mut board, 0, 0, &[1, 2]);
+    keep_candidates(&mut board, 0, 1, &[1, 2]);
+    for d in 1..=2 {
+        board.eliminate_candidate(2, 3, d);
+        board.eliminate_candidate(1, 6, d);
+    }
+    for r in 3..9 {
+        for c in [2, 3, 6] {
+            if !rows_of_one.contains(&r) {
+                board.eliminate_candidate(r, c, 1);
+            }
+            if r > 4 {
+                board.eliminate_candidate(r, c, 2);
+            }
+        }
+    }
+    board
+}
+
+#[test]
+fn junior_exocet_strategy() {
+    let mut board = exocet_board(&[3, 4]);
+    let strat = sudoku_evaluator::strategy::exocet::JuniorExocet;
+    assert!(strat.apply(&mut board).unwrap());
+    assert_eq!(board.candidates(1, 3), vec![1, 2]);
+    assert_eq!(board.candidates(2, 6), vec![1, 2]);
+}
+
+#[test]
+fn exocet_strategy() {
+    // digit 1 spans four rows of the cross-lines: the box of rows 3-5 in
+    // column 2 and row 6 in columns 3 and 6
+    let mut board = exocet_board(&[3, 4, 5, 6]);
+    for r in 3..6 {
+        for c in [3, 6] {
+            board.eliminate_candidate(r, c, 1);
+        }
+    }
+    board.eliminate_candidate(6, 2, 1);
+    let junior = sudoku_evaluator::strategy::exocet::JuniorExocet;
+    assert!(!junior.apply(&mut board).unwrap());
+    let strat = sudoku_evaluator::strategy::exocet::Exocet;
+    assert!(strat.apply(&mut board).unwrap());
+    assert_eq!(board.candidates(1, 3), vec![1, 2]);
+}
+
+#[test]
+fn pattern_overlay_strategy() {
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    // rows 0 and 1 only keep digit 1 in columns 0 and 3, so every template
+    // uses both columns there
+    for r in 0..2 {
+        for c in (0..9).filter(|&c| c != 0 && c != 3) {
+            board.eliminate_candidate(r, c, 1);
+        }
+    }
+    let strat = sudoku_evaluator::strategy::pattern_overlay::PatternOverlay::new();
+    assert!(strat.apply(&mut board).unwrap());
+    for r in 2..9 {
+        assert!(!board.candidates(r, 0).contains(1));
+        assert!(!board.candidates(r, 3).contains(1));
+    }
+    assert!(board.candidates(3, 1).contains(1));
+}
+
+#[test]
+fn pattern_overlay_pair_combination() {
+    use sudoku_evaluator::strategy::pattern_overlay::PatternOverlay;
+    // 2 keeps a single template, 1 the cells of that template and of another
+    // one, so only templates avoiding the first leave room for both digits
+    let first = [0, 3, 6, 1, 4, 7, 2, 5, 8];
+    let second = [1, 4, 7, 2, 5, 8, 0, 3, 6];
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    for r in 0..9 {
+        for c in 0..9 {
+            if c != first[r] {
+                board.eliminate_candidate(r, c, 2);
+                if c != second[r] {
+                    board.eliminate_candidate(r, c, 1);
+                }
+            }
+        }
+    }
+    assert!(!PatternOverlay::new().apply(&mut board.clone()).unwrap());
+    assert!(
+        PatternOverlay::with_pair_combination()
+            .apply(&mut board)
+            .unwrap()
+    );
+    for r in 0..9 {
+        assert!(!board.candidates(r, first[r]).contains(1));
+        assert!(board.candidates(r, second[r]).contains(1));
+    }
+}
+
+#[test]
+fn pattern_overlay_reports_digit_without_templates() {
+    use sudoku_evaluator::SolverError;
+    use sudoku_evaluator::strategy::pattern_overlay::PatternOverlay;
+    // the top three rows keep 1 only in the top left box
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    for r in 0..3 {
+        for c in 3..9 {
+            board.eliminate_candidate(r, c, 1);
+        }
+    }
+    match PatternOverlay::new().apply(&mut board) {
+        Err(SolverError::Contradiction {
+            row, col, digit, ..
+        }) => {
+            assert_eq!((row, col, digit), (0, 0, Some(1)));
+        }
+        other => panic!("expected contradiction, got {:?}", other),
+    }
+}
+
+#[test]
+fn nishio_strategy() {
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    // rows 0 and 1 only keep digit 1 in columns 0 and 3: placing it at (2,0)
+    // leaves row 1 without a position
+    for r in 0..2 {
+        for c in (0..9).filter(|&c| c != 0 && c != 3) {
+            board.eliminate_candidate(r, c, 1);
+        }
+    }
+    let strat = sudoku_evaluator::strategy::nishio::Nishio;
+    assert!(strat.apply(&mut board).unwrap());
+    assert!(!board.candidates(2, 0).contains(1));
+}
+
+/// Cells (0,0) {1,2}, (0,8) {1,3} and (8,0) {2,3}: whatever (0,0) holds,
+/// one of the other two becomes 3.
+fn forcing_chain_board() -> Board {
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    keep_candidates(&mut board, 0, 0, &[1, 2]);
+    keep_candidates(&mut board, 0, 8, &[1, 3]);
+    keep_candidates(&mut board, 8, 0, &[2, 3]);
+    board
+}
+
+#[test]
+fn cell_forcing_chain_strategy() {
+    use sudoku_evaluator::strategy::forcing_chain::{CellForcingChain, ChainDepth};
+    let mut board = forcing_chain_board();
+    let strat = CellForcingChain::new(ChainDepth::Singles);
+    assert!(strat.apply(&mut board).unwrap());
+    assert!(!board.candidates(8, 8).contains(3));
+}
+
+#[test]
+fn unit_forcing_chain_strategy() {
+    use sudoku_evaluator::strategy::forcing_chain::{ChainDepth, UnitForcingChain};
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    // digit 1 is at (0,0) or (0,8), and either way (4,0) and (4,8) hold 1 and 5
+    for c in 1..8 {
+        board.eliminate_candidate(0, c, 1);
+    }
+    for c in [0, 8] {
+        for d in (2..=9).filter(|&d| d != 5) {
+            board.eliminate_candidate(4, c, d);
+        }
+    }
+    let strat = UnitForcingChain::new(ChainDepth::Singles);
+    assert!(strat.apply(&mut board).unwrap());
+    assert!(!board.candidates(4, 4).contains(5));
+}
+
+#[test]
+fn dynamic_forcing_chain_strategy() {
+    use sudoku_evaluator::strategy::forcing_chain::{ChainDepth, DynamicForcingChain};
+    let mut board = forcing_chain_board();
+    let strat = DynamicForcingChain::new(ChainDepth::Singles);
+    assert!(strat.apply(&mut board).unwrap());
+    assert!(!board.candidates(8, 8).contains(3));
+}
+
+#[test]
+fn kraken_fish_strategy() {
+    use sudoku_evaluator::strategy::forcing_chain::ChainDepth;
+    use sudoku_evaluator::strategy::kraken_fish::KrakenFish;
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    // X-Wing on digit 1 in rows 0 and 4, columns 0 and 4, with a fin at (4,8).
+    // The fin removes 1 from (3,6), leaving (7,6) as the only 1 of column 6.
+    for c in (0..9).filter(|&c| c != 0 && c != 4) {
+        board.eliminate_candidate(0, c, 1);
+    }
+    for c in (0..9).filter(|&c| c != 0 && c != 4 && c != 8) {
+        board.eliminate_candidate(4, c, 1);
+    }
+    for r in (0..9).filter(|&r| r != 3 && r != 7) {
+        board.eliminate_candidate(r, 6, 1);
+    }
+    let strat = KrakenFish::new(ChainDepth::Singles);
+    assert!(strat.apply(&mut board).unwrap());
+    assert!(!board.candidates(7, 0).contains(1));
+}
+
+/// Base cells (0,0) {1,3} and (0,4) {2,3}. The almost locked set (4,0) {1,5},
+/// (4,4) {2,5} excludes 1 with 2, and the one made of (8,0) {1,6} and (8,4)
+/// {3,6} excludes 1 with 3, so the base cells can only hold 3 and 2.
+fn aligned_exclusion_board() -> Board {
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    keep_candidates(&mut board, 0, 0, &[1, 3]);
+    keep_candidates(&mut board, 0, 4, &[2, 3]);
+    keep_candidates(&mut board, 4, 0, &[1, 5]);
+    keep_candidates(&mut board, 4, 4, &[2, 5]);
+    keep_candidates(&mut board, 8, 0, &[1, 6]);
+    keep_candidates(&mut board, 8, 4, &[3, 6]);
+    board
+}
+
+#[test]
+fn aligned_pair_exclusion_strategy() {
+    let mut board = aligned_exclusion_board();
+    let strat = sudoku_evaluator::strategy::aligned_exclusion::AlignedPairExclusion;
+    assert!(strat.apply(&mut board).unwrap());
+    while strat.apply(&mut board).unwrap() {}
+    assert_eq!(board.candidates(0, 4), vec![2]);
+}
+
+#[test]
+fn aligned_triple_exclusion_strategy() {
+    let mut board = aligned_exclusion_board();
+    let strat = sudoku_evaluator::strategy::aligned_exclusion::AlignedTripleExclusion;
+    assert!(strat.apply(&mut board).unwrap());
+    while strat.apply(&mut board).unwrap() {}
+    // the almost locked sets of column 4 leave the triple of column 0 a
+    // single combination
+    assert_eq!(board.candidates(0, 0), vec![3]);
+    assert_eq!(board.candidates(4, 0), vec![1]);
+    assert_eq!(board.candidates(8, 0), vec![6]);
+}
+
+/// Easter Monster, whose rows 1 and 7 and columns 1 and 7 carry an SK loop.
+const EASTER_MONSTER: &str =
+    "1.......2.9.4...5...6...7...5.9.3.......7.......85..4.7.....6...3...9.8...2.....1";
+
+#[test]
+fn sk_loop_strategy() {
+    let mut board = Board::parse(EASTER_MONSTER).unwrap();
+    let strat = sudoku_evaluator::strategy::multi_sector_locked_set::SkLoop;
+    assert!(strat.apply(&mut board).unwrap());
+    assert!(!board.candidates(1, 4).contains(3));
+    assert!(!board.candidates(1, 4).contains(8));
+}
+
+#[test]
+fn multi_sector_locked_set_strategy() {
+    let mut board = Board::parse(EASTER_MONSTER).unwrap();
+    let strat = sudoku_evaluator::strategy::multi_sector_locked_set::MultiSectorLockedSet;
+    assert!(strat.apply(&mut board).unwrap());
+    assert!(!board.candidates(4, 6).contains(1));
+}
+
+#[test]
+fn solve_report_counts_steps() {
+    let puzzle =
+        "530070000600195000098000060800060003400803001700020006060000280000419005000080079";
+    let mut board = Board::parse(puzzle).unwrap();
+    let unsolved = board.unsolved_cells().count();
+    let report = Solver::default().solve_with_report(&mut board).unwrap();
+    assert!(board.is_solved());
+    let placements: usize = report.usages.iter().map(|u| u.placements).sum();
+    assert_eq!(placements, unsolved);
+    let applications: usize = report.usages.iter().map(|u| u.applications).sum();
+    assert_eq!(applications, report.steps);
+    let singles = report.usage(StrategyKind::SingleCandidate).unwrap();
+    assert_eq!(singles.first_step, 0);
+    assert_eq!(singles.unsolved_at_first_use, unsolved);
+    assert_eq!(singles.eliminations, 0);
+    assert!(report.hardest_step.is_some());
+}
+
+#[test]
+fn solve_report_counts_eliminations() {
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    // digit 1 only in column 0 of box 0, as in the pointing triple test
+    for r in 0..3 {
+        for c in 1..3 {
+            board.eliminate_candidate(r, c, 1);
+        }
+    }
+    let solver = Solver::new(vec![Box::new(
+        sudoku_evaluator::strategy::locked_candidates::LockedCandidatesType1,
+    )]);
+    let report = solver.reduce_with_report(&mut board).unwrap();
+    let usage = report.usage(StrategyKind::LockedCandidatesType1).unwrap();
+    assert_eq!(usage.placements, 0);
+    assert_eq!(usage.eliminations, 6);
+    assert_eq!(usage.applications, report.steps);
+}
+
+#[test]
+fn every_policy_solves() {
+    use sudoku_evaluator::SolvePolicy;
+    let puzzle =
+        "..467.....7..9...8.9..4.5.7.5...1.234.6..37...........9...372.4.8.4.9.3...5.8...9";
+    let mut expected = Board::parse(puzzle).unwrap();
+    Solver::default().solve(&mut expected).unwrap();
+    let report = |policy| {
+        let mut board = Board::parse(puzzle).unwrap();
+        let solver = Solver::default().with_policy(policy);
+        let report = solver.solve_with_report(&mut board).unwrap();
+        assert_eq!(board.to_string(), expected.to_string(), "{}", policy);
+        let hardest = report.hardest_step.unwrap();
+        assert_eq!(
+            Some(report.trace[hardest].kind),
+            report.hardest(),
+            "{}",
+            policy
+        );
+        assert!(
+            report.trace[..hardest]
+                .iter()
+                .all(|step| Some(step.kind) != report.hardest())
+        );
+        report
+    };
+    let simplest = report(SolvePolicy::SimplestFirst);
+    let round_robin = report(SolvePolicy::RoundRobin);
+    let batched = report(SolvePolicy::AllPerPass);
+    let ladder = report(SolvePolicy::MinimalLadder);
+    // batching every single found in a pass takes fewer steps
+    assert!(batched.steps < simplest.steps);
+    assert_eq!(batched.hardest(), simplest.hardest());
+    // cycling reaches harder strategies before the simple ones run dry
+    let difficulty = |r: &sudoku_evaluator::SolveReport| r.hardest().unwrap().difficulty();
+    assert!(difficulty(&round_robin) > difficulty(&simplest));
+    assert_eq!(ladder.hardest(), simplest.hardest());
+}
+
+#[test]
+fn canonical_order_is_shared() {
+    use sudoku_evaluator::strategy::ALL_KINDS;
+    assert_eq!(Solver::default().kinds(), ALL_KINDS.to_vec());
+    let ascending = |kinds: &[StrategyKind]| {
+        kinds
+            .windows(2)
+            .all(|w| w[0].difficulty() < w[1].difficulty())
+    };
+    assert!(ascending(&[
+        StrategyKind::XWing,
+        StrategyKind::XYZWing,
+        StrategyKind::Swordfish,
+        StrategyKind::UniqueRectangle,
+        StrategyKind::Jellyfish,
+    ]));
+    assert!(ascending(&[
+        StrategyKind::PatternOverlay,
+        StrategyKind::Nishio,
+        StrategyKind::KrakenFish,
+        StrategyKind::CellForcingChain,
+    ]));
+}
+
+#[test]
+fn single_candidate_lists_every_single() {
+    use sudoku_evaluator::strategy::{Deduction, single_candidate::SingleCandidate};
+    let puzzle =
+        ".3467891267219534819834256785976142342685.79171392485696153728428741963534528617.";
+    let board = Board::parse(puzzle).unwrap();
+    let deductions = SingleCandidate.deductions(&board).unwrap();
+    assert_eq!(deductions.len(), 3);
+    assert!(deductions.contains(&Deduction::Place(0, 0, 5)));
+    assert!(deductions.contains(&Deduction::Place(4, 5, 3)));
+    assert!(deductions.contains(&Deduction::Place(8, 8, 9)));
+}
+
+#[test]
+fn all_per_pass_groups_deductions() {
+    use sudoku_evaluator::SolvePolicy;
+    let puzzle =
+        "530070000600195000098000060800060003400803001700020006060000280000419005000080079";
+    let singles = || Solver::builder().kind(StrategyKind::SingleCandidate);
+    let mut one_at_a_time = Board::parse(puzzle).unwrap();
+    let single = singles()
+        .build()
+        .solve_with_report(&mut one_at_a_time)
+        .unwrap();
+    let mut batched = Board::parse(puzzle).unwrap();
+    let grouped = singles()
+        .policy(SolvePolicy::AllPerPass)
+        .build()
+        .solve_with_report(&mut batched)
+        .unwrap();
+    assert_eq!(batched.to_string(), one_at_a_time.to_string());
+    assert!(grouped.steps < single.steps);
+    assert_eq!(grouped.trace.len(), grouped.steps);
+    assert!(grouped.trace.iter().any(|step| step.placements.len() > 1));
+    let placements: usize = grouped.trace.iter().map(|s| s.placements.len()).sum();
+    let unsolved = Board::parse(puzzle).unwrap().unsolved_cells().count();
+    assert_eq!(placements, unsolved);
+}
+
+#[test]
+fn step_budget_stops_solve() {
+    use sudoku_evaluator::{Budget, SolverError};
+    let puzzle =
+        "530070000600195000098000060800060003400803001700020006060000280000419005000080079";
+    let mut board = Board::parse(puzzle).unwrap();
+    let unsolved = board.unsolved_cells().count();
+    let solver = Solver::default().with_budget(Budget::default().with_max_steps(3));
+    match solver.solve(&mut board) {
+        Err(SolverError::BudgetExceeded { steps, strategies }) => {
+            assert_eq!(steps, 3);
+            assert_eq!(strategies, vec![StrategyKind::SingleCandidate]);
+        }
+        other => panic!("expected budget exceeded, got {:?}", other),
+    }
+    assert_eq!(board.unsolved_cells().count(), unsolved - 3);
+}
+
+#[test]
+fn time_budget_interrupts_forcing_chain() {
+    use std::time::{Duration, Instant};
+    use sudoku_evaluator::strategy::forcing_chain::{ChainDepth, DynamicForcingChain};
+    use sudoku_evaluator::{Budget, SolverError};
+    let puzzle =
+        "100007090030020008009600500005300900010080002600004000300000010040000007007000300";
+    let mut board = Board::parse(puzzle).unwrap();
+    let solver = Solver::builder()
+        .kind(StrategyKind::SingleCandidate)
+        .kind(StrategyKind::HiddenSingle)
+        .strategy(Box::new(DynamicForcingChain::new(ChainDepth::Full)))
+        .budget(Budget::default().with_time_limit(Duration::from_millis(100)))
+        .build();
+    let start = Instant::now();
+    let err = solver.solve(&mut board).unwrap_err();
+    assert!(matches!(err, SolverError::BudgetExceeded { .. }), "{}", err);
+    assert!(start.elapsed() < Duration::from_secs(10));
+    assert!(board.is_valid());
+}
+
+#[test]
+fn cancelled_token_stops_solve() {
+    use sudoku_evaluator::{CancelToken, SolverError};
+    let puzzle =
+        "530070000600195000098000060800060003400803001700020006060000280000419005000080079";
+    let mut board = Board::parse(puzzle).unwrap();
+    let token = CancelToken::new();
+    token.cancel();
+    let solver = Solver::default().with_cancel_token(token);
+    let err = solver.solve(&mut board).unwrap_err();
+    assert!(
+        matches!(err, SolverError::Cancelled { steps: 0, .. }),
+        "{}",
+        err
+    );
+    assert_eq!(board.to_string(), Board::parse(puzzle).unwrap().to_string());
+}
+
+#[test]
+fn cancel_interrupts_forcing_chain() {
+    use std::time::{Duration, Instant};
+    use sudoku_evaluator::strategy::forcing_chain::{ChainDepth, DynamicForcingChain};
+    use sudoku_evaluator::{CancelToken, SolverError};
+    let puzzle =
+        "100007090030020008009600500005300900010080002600004000300000010040000007007000300";
+    let mut board = Board::parse(puzzle).unwrap();
+    let token = CancelToken::new();
+    let solver = Solver::builder()
+        .kind(StrategyKind::SingleCandidate)
+        .kind(StrategyKind::HiddenSingle)
+        .strategy(Box::new(DynamicForcingChain::new(ChainDepth::Full)))
+        .cancel_token(token.clone())
+        .build();
+    let canceller = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(100));
+        token.cancel();
+    });
+    let start = Instant::now();
+    let err = solver.solve(&mut board).unwrap_err();
+    canceller.join().unwrap();
+    assert!(matches!(err, SolverError::Cancelled { .. }), "{}", err);
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+fn observer_sees_every_application() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::time::Duration;
+    use sudoku_evaluator::{SolveObserver, SolvePolicy};
+
+    #[derive(Default)]
+    struct Recorder {
+        before: RefCell<Vec<StrategyKind>>,
+        after: RefCell<Vec<(StrategyKind, bool)>>,
+    }
+
+    impl SolveObserver for Recorder {
+        fn before_apply(&self, kind: StrategyKind) {
+            self.before.borrow_mut().push(kind);
+        }
+
+        fn after_apply(&self, kind: StrategyKind, changed: bool, _elapsed: Duration) {
+            self.after.borrow_mut().push((kind, changed));
+        }
+    }
+
+    let puzzle =
+        "530070000600195000098000060800060003400803001700020006060000280000419005000080079";
+    for policy in [SolvePolicy::SimplestFirst, SolvePolicy::AllPerPass] {
+        let recorder = Rc::new(Recorder::default());
+        let solver = Solver::builder()
+            .kind(StrategyKind::SingleCandidate)
+            .kind(StrategyKind::HiddenSingle)
+            .policy(policy)
+            .observer(recorder.clone())
+            .build();
+        let mut board = Board::parse(puzzle).unwrap();
+        let report = solver.solve_with_report(&mut board).unwrap();
+        let before = recorder.before.borrow();
+        let after = recorder.after.borrow();
+        assert_eq!(before.len(), after.len());
+        assert!(before.iter().zip(after.iter()).all(|(b, a)| *b == a.0));
+        let changed = after.iter().filter(|(_, changed)| *changed).count();
+        assert_eq!(changed, report.steps, "{}", policy);
+    }
+}
+
+#[test]
+fn step_budget_fitting_the_solve_succeeds() {
+    use sudoku_evaluator::{Budget, SolverError};
+    let puzzle =
+        "530070000600195000098000060800060003400803001700020006060000280000419005000080079";
+    let mut board = Board::parse(puzzle).unwrap();
+    let needed = Solver::default()
+        .solve_with_report(&mut board)
+        .unwrap()
+        .steps;
+
+    let mut board = Board::parse(puzzle).unwrap();
+    let solver = Solver::default().with_budget(Budget::default().with_max_steps(needed));
+    let report = solver.solve_with_report(&mut board).unwrap();
+    assert_eq!(report.steps, needed);
+
+    let mut board = Board::parse(puzzle).unwrap();
+    let solver = Solver::default().with_budget(Budget::default().with_max_steps(needed - 1));
+    let err = solver.solve(&mut board).unwrap_err();
+    assert!(matches!(err, SolverError::BudgetExceeded { steps, .. } if steps == needed - 1));
+    assert_eq!(board.unsolved_cells().count(), 1);
+}
+
+#[test]
+fn observer_hears_about_failed_application() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::time::Duration;
+    use sudoku_evaluator::strategy::naked_pair::NakedPair;
+    use sudoku_evaluator::{SolveObserver, SolverError};
+
+    #[derive(Default)]
+    struct Balance {
+        started: Cell<usize>,
+        finished: Cell<usize>,
+        failed: Cell<usize>,
+    }
+
+    impl SolveObserver for Balance {
+        fn before_apply(&self, _kind: StrategyKind) {
+            self.started.set(self.started.get() + 1);
+        }
+
+        fn after_apply(&self, _kind: StrategyKind, _changed: bool, _elapsed: Duration) {
+            self.finished.set(self.finished.get() + 1);
+        }
+
+        fn apply_failed(&self, kind: StrategyKind, error: &SolverError, _elapsed: Duration) {
+            assert_eq!(kind, StrategyKind::NakedPair);
+            assert!(matches!(error, SolverError::Contradiction { .. }));
+            self.failed.set(self.failed.get() + 1);
+        }
+    }
+
+    // a naked pair of 1 and 2 in row 0 next to a cell left with only 1
+    let mut board = Board::parse(&".".repeat(81)).unwrap();
+    for c in 0..3 {
+        for d in 3..=9 {
+            board.eliminate_candidate(0, c, d);
+        }
+    }
+    board.eliminate_candidate(0, 2, 2);
+    let balance = Rc::new(Balance::default());
+    let solver = Solver::new(vec![Box::new(NakedPair)]).with_observer(balance.clone());
+    assert!(solver.reduce(&mut board).is_err());
+    assert_eq!(balance.failed.get(), 1);
+    assert_eq!(
+        balance.started.get(),
+        balance.finished.get() + balance.failed.get()
+    );
+}
+
+#[test]
+fn cancelled_solve_leaves_solvers_run_by_observers_alone() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::time::Duration;
+    use sudoku_evaluator::{CancelToken, SolveObserver, SolverError};
+
+    // cancels the solve it watches, then solves another puzzle of its own
+    struct Nested {
+        token: CancelToken,
+        outcome: RefCell<Option<Result<Vec<StrategyKind>, SolverError>>>,
+    }
+
+    impl SolveObserver for Nested {
+        fn after_apply(&self, _kind: StrategyKind, changed: bool, _elapsed: Duration) {
+            if changed && self.outcome.borrow().is_none() {
+                self.token.cancel();
+                let mut board = Board::parse(PUZZLE).unwrap();
+                *self.outcome.borrow_mut() = Some(Solver::default().solve(&mut board));
+            }
+        }
+    }
+
+    const PUZZLE: &str =
+        "530070000600195000098000060800060003400803001700020006060000280000419005000080079";
+    let token = CancelToken::new();
+    let nested = Rc::new(Nested {
+        token: token.clone(),
+        outcome: RefCell::new(None),
+    });
+    let solver = Solver::default()
+        .with_cancel_token(token)
+        .with_observer(nested.clone());
+    let mut board = Board::parse(PUZZLE).unwrap();
+    let err = solver.solve(&mut board).unwrap_err();
+    assert!(
+        matches!(err, SolverError::Cancelled { steps: 1, .. }),
+        "{}",
+        err
+    );
+    let outcome = nested.outcome.borrow_mut().take().unwrap();
+    assert!(outcome.is_ok(), "{:?}", outcome);
+}
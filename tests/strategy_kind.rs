@@ -0,0 +1,48 @@
+use sudoku_evaluator::strategy::{ALL_KINDS, Strategy, StrategyFamily, StrategyKind};
+
+#[test]
+fn names_round_trip() {
+    for kind in ALL_KINDS {
+        assert_eq!(kind.to_string().parse::<StrategyKind>(), Ok(kind));
+        assert_eq!(format!("{:?}", kind).parse::<StrategyKind>(), Ok(kind));
+        for alias in kind.info().aliases {
+            assert_eq!(alias.parse::<StrategyKind>(), Ok(kind));
+        }
+    }
+}
+
+#[test]
+fn names_and_aliases_parse() {
+    assert_eq!("x-wing".parse(), Ok(StrategyKind::XWing));
+    assert_eq!("XWing".parse(), Ok(StrategyKind::XWing));
+    assert_eq!("X_Wing".parse(), Ok(StrategyKind::XWing));
+    assert_eq!("pointing".parse(), Ok(StrategyKind::LockedCandidatesType1));
+    assert_eq!("bug+n".parse(), Ok(StrategyKind::Bug));
+    assert_eq!("bug+1".parse(), Ok(StrategyKind::Bug));
+    assert_eq!("MSLS".parse(), Ok(StrategyKind::MultiSectorLockedSet));
+    let err = "guess".parse::<StrategyKind>().unwrap_err();
+    assert_eq!(err.to_string(), "unknown strategy `guess`");
+}
+
+#[test]
+fn metadata_per_kind() {
+    assert_eq!(StrategyKind::ThreeDMedusa.name(), "3d-medusa");
+    assert_eq!(StrategyKind::Swordfish.family(), StrategyFamily::Fish);
+    assert!(StrategyKind::UniqueRectangleType4.assumes_uniqueness());
+    assert!(StrategyKind::Bug.assumes_uniqueness());
+    assert!(!StrategyKind::XYChain.assumes_uniqueness());
+    for kind in ALL_KINDS {
+        assert!(!kind.description().is_empty());
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn box_line_reduction_means_pointing() {
+    use sudoku_evaluator::strategy::box_line_reduction::BoxLineReduction;
+    // the old type and the old name both stand for locked candidates type 1
+    assert_eq!(BoxLineReduction.kind(), StrategyKind::LockedCandidatesType1);
+    for name in ["box-line-reduction", "BoxLineReduction"] {
+        assert_eq!(name.parse(), Ok(BoxLineReduction.kind()));
+    }
+}